@@ -4,6 +4,7 @@ pub type Type = usize; // 牌の種別部分 (萬子,筒子,索子,字牌,花牌
 pub type Tnum = usize; // 牌の数字部分 (1~9)
 pub type Dora = usize; // ドラの飜数 (0以上)
 pub type Index = usize; // その他Index
+pub type Shanten = i32; // 向聴数 (聴牌は0, 和了形は-1)
 
 pub type Score = i32; // プレイヤーの持ち点
 pub type Point = i32; // 打点
@@ -11,6 +12,7 @@ pub type Points = (Point, Point, Point); // (ロンの支払い, ツモ・子の
 
 // Number
 pub const SEAT: usize = 4; // 座席の数
+pub const SEAT_SANMA: usize = 3; // 三人麻雀の座席の数
 pub const TYPE: usize = 5; // 牌の種別部分の数 (萬子,筒子,索子,字牌,花牌)
 pub const TNUM: usize = 10; // 牌の数字部分の数 (1~9、0は未使用)
 pub const TILE: usize = 4; // 同種の牌の数
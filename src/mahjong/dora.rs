@@ -1,12 +1,14 @@
 use super::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DoraInfo {
     pub dora_fan_sum: usize,
     pub dora_fan: usize,
     pub ura_dora_fan: usize,
     pub aka_dora_fan: usize,
     pub nuki_dora_fan: usize,
+    pub flower_fan: usize,
 }
 
 impl DoraInfo {
@@ -15,18 +17,91 @@ impl DoraInfo {
         ura_dora_fan: usize,
         aka_dora_fan: usize,
         nuki_dora_fan: usize,
+        flower_fan: usize,
     ) -> Self {
         Self {
-            dora_fan_sum: dora_fan + ura_dora_fan + aka_dora_fan + nuki_dora_fan,
+            dora_fan_sum: dora_fan + ura_dora_fan + aka_dora_fan + nuki_dora_fan + flower_fan,
             dora_fan,
             ura_dora_fan,
             aka_dora_fan,
             nuki_dora_fan,
+            flower_fan,
         }
     }
 }
 
-pub fn calc_dora(ctx: &HandContext) -> DoraInfo {
+// 花牌の飜数の設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlowerRules {
+    pub fan_per_flower: usize, // 花牌1枚ごとの飜
+    pub own_seat_fan: usize,   // 自風に対応する花牌 (東家なら春と梅) 1枚ごとの追加の飜
+    pub full_set_fan: usize,   // 春夏秋冬, 梅蘭菊竹 のいずれかが揃った場合の追加の飜 (1組ごと)
+}
+
+impl Default for FlowerRules {
+    fn default() -> Self {
+        Self {
+            fan_per_flower: 1,
+            own_seat_fan: 0,
+            full_set_fan: 0,
+        }
+    }
+}
+
+pub fn calc_flower(flowers: &[Tile], jikaze: Tnum, rules: &FlowerRules) -> usize {
+    let mut fan = 0;
+    let mut has = [false; TNUM];
+    for t in flowers {
+        if !t.is_flower() {
+            continue;
+        }
+        fan += rules.fan_per_flower;
+        if t.1 == jikaze || t.1 == jikaze + 4 {
+            fan += rules.own_seat_fan;
+        }
+        has[t.1] = true;
+    }
+    if has[FSP] && has[FSU] && has[FAU] && has[FWI] {
+        fan += rules.full_set_fan;
+    }
+    if has[FPL] && has[FOR] && has[FCH] && has[FBA] {
+        fan += rules.full_set_fan;
+    }
+    fan
+}
+
+// ドラ表示牌からドラを返却
+// 数牌は 9→1, 風牌は 東→南→西→北→東, 三元牌は 白→發→中→白 の順
+// 三人麻雀では萬子は 1→9, 9→1 となる
+// 花牌は 春夏秋冬, 梅蘭菊竹 それぞれの中で循環し, オールマイティはオールマイティを表す
+pub fn indicator_to_dora(indicator: &Tile, sanma: bool) -> Tile {
+    let Tile(t, n) = *indicator;
+    let next = match t {
+        TM if sanma => {
+            if n == 1 {
+                9
+            } else {
+                1
+            }
+        }
+        TM | TP | TS => n % 9 + 1,
+        TZ => match n {
+            WEA..=WNO => n % 4 + 1,
+            DWH..=DRE => (n - DWH + 1) % 3 + DWH,
+            _ => n,
+        },
+        TH => match n {
+            FSP..=FWI => n % 4 + 1,
+            FPL..=FBA => (n - FPL + 1) % 4 + FPL,
+            _ => n,
+        },
+        _ => n,
+    };
+    Tile(t, next)
+}
+
+pub fn calc_dora(ctx: &HandContext, rules: &Ruleset) -> DoraInfo {
     let mut dora_fan: usize = 0;
     let mut ura_dora_fan: usize = 0;
     let mut aka_dora_fan: usize = 0;
@@ -52,6 +127,15 @@ pub fn calc_dora(ctx: &HandContext) -> DoraInfo {
     for TileWithDora(tile, d) in &ctx.nuki_dora {
         hand[tile.0][tile.1] += 1;
         nuki_dora_fan += d;
+        // 三人麻雀では北を抜きドラとする
+        if let Some(sanma) = &rules.sanma {
+            if *tile == Tile(TZ, WNO) {
+                nuki_dora_fan += sanma.nuki_fan;
+            }
+        }
+    }
+    for tile in &ctx.flowers {
+        hand[tile.0][tile.1] += 1;
     }
 
     for tile in &ctx.dora {
@@ -61,5 +145,53 @@ pub fn calc_dora(ctx: &HandContext) -> DoraInfo {
         ura_dora_fan += hand[tile.0][tile.1];
     }
 
-    DoraInfo::new(dora_fan, ura_dora_fan, aka_dora_fan, nuki_dora_fan)
+    let flower_fan = calc_flower(&ctx.flowers, ctx.jikaze, &rules.flower);
+
+    DoraInfo::new(
+        dora_fan,
+        ura_dora_fan,
+        aka_dora_fan,
+        nuki_dora_fan,
+        flower_fan,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indicator_to_dora_works_01() {
+        assert_eq!(indicator_to_dora(&Tile(TP, 3), false), Tile(TP, 4));
+        assert_eq!(indicator_to_dora(&Tile(TS, 9), false), Tile(TS, 1));
+        assert_eq!(indicator_to_dora(&Tile(TM, 9), false), Tile(TM, 1));
+        assert_eq!(indicator_to_dora(&Tile(TM, 1), false), Tile(TM, 2));
+    }
+
+    #[test]
+    fn indicator_to_dora_works_02() {
+        assert_eq!(indicator_to_dora(&Tile(TZ, WEA), false), Tile(TZ, WSO));
+        assert_eq!(indicator_to_dora(&Tile(TZ, WNO), false), Tile(TZ, WEA));
+        assert_eq!(indicator_to_dora(&Tile(TZ, DWH), false), Tile(TZ, DGR));
+        assert_eq!(indicator_to_dora(&Tile(TZ, DRE), false), Tile(TZ, DWH));
+    }
+
+    #[test]
+    fn indicator_to_dora_works_03() {
+        // 三人麻雀の萬子
+        assert_eq!(indicator_to_dora(&Tile(TM, 1), true), Tile(TM, 9));
+        assert_eq!(indicator_to_dora(&Tile(TM, 9), true), Tile(TM, 1));
+        assert_eq!(indicator_to_dora(&Tile(TP, 9), true), Tile(TP, 1));
+    }
+
+    #[test]
+    fn indicator_to_dora_works_04() {
+        // 花牌とオールマイティ
+        assert_eq!(indicator_to_dora(&Tile(TH, FWI), false), Tile(TH, FSP));
+        assert_eq!(indicator_to_dora(&Tile(TH, FSU), false), Tile(TH, FAU));
+        assert_eq!(indicator_to_dora(&Tile(TH, FBA), false), Tile(TH, FPL));
+        assert_eq!(indicator_to_dora(&Tile(TZ, ALM), false), Tile(TZ, ALM));
+        // 範囲外の番号はそのまま
+        assert_eq!(indicator_to_dora(&Tile(TH, 0), false), Tile(TH, 0));
+    }
 }
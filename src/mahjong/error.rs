@@ -0,0 +1,63 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MahjongError {
+    InvalidTileSymbol(String),     // 牌の表記が不正 (例: z9, x1, 123)
+    InvalidMeld(String),           // 副露が不正
+    InvalidTileCount(usize),       // 牌の枚数が不正 (鳴き・アガり牌を含めた枚数)
+    InvalidMentsuCount(usize),     // 採点モードで扱えない和了形の面子の数
+    TooManyCopies(Tile),           // 同じ牌が TILE 枚を超えている
+    DuplicateFlower(Tile),         // 同じ花牌が2枚以上ある
+    UnavailableTile(Tile),         // ルール上使用しない牌 (三人麻雀の萬子の2~8など)
+    TooManyRedFives(Tile),         // 赤五がルールの枚数を超えている
+    NotWinning,                    // 和了形になっていない
+    NoYaku,                        // 役がない
+    DoraOnly(usize),               // 役がなくドラ (飜) のみ
+    InsufficientFan(usize, usize), // 縛りを満たしていない (必要な飜, 役の飜)
+    KuitanNotAllowed,              // 喰いタンなしのルールで鳴いた断幺九以外に役がない
+    Atozuke(Tile),                 // 後付けなしのルールで役がない待ち牌がある
+    InvalidFu(usize),              // 点数表にない符
+    InvalidYakumanCount(usize),    // 扱えない役満倍数
+    InvalidSeat(Seat),             // 座席番号が不正 (人数を超える, 和了者と放銃者が同じ)
+    DealerMismatch(Seat),          // 点数の親子と和了者が親かどうかが一致しない (和了者)
+    InvalidRuleset(String),        // ルール設定の読み込みに失敗
+}
+
+impl fmt::Display for MahjongError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MahjongError::InvalidTileSymbol(s) => write!(f, "invalid tile symbol: {}", s),
+            MahjongError::InvalidMeld(s) => write!(f, "invalid meld: {}", s),
+            MahjongError::InvalidTileCount(n) => write!(f, "invalid tile count: {}", n),
+            MahjongError::InvalidMentsuCount(n) => write!(f, "invalid mentsu count: {}", n),
+            MahjongError::TooManyCopies(t) => write!(f, "too many copies of {}", t),
+            MahjongError::DuplicateFlower(t) => write!(f, "duplicate flower: {}", t),
+            MahjongError::UnavailableTile(t) => write!(f, "unavailable tile: {}", t),
+            MahjongError::TooManyRedFives(t) => write!(f, "too many red fives: {}", t),
+            MahjongError::NotWinning => write!(f, "not a winning hand"),
+            MahjongError::NoYaku => write!(f, "no yaku"),
+            MahjongError::DoraOnly(n) => write!(f, "no yaku (dora only: {})", n),
+            MahjongError::InsufficientFan(min, fan) => {
+                write!(f, "insufficient fan: {} (requires {})", fan, min)
+            }
+            MahjongError::KuitanNotAllowed => write!(f, "open tanyao is not allowed"),
+            MahjongError::Atozuke(t) => write!(f, "atozuke: no yaku when winning on {}", t),
+            MahjongError::InvalidFu(fu) => write!(f, "invalid fu: {}", fu),
+            MahjongError::InvalidYakumanCount(n) => write!(f, "invalid yakuman count: {}", n),
+            MahjongError::InvalidSeat(s) => write!(f, "invalid seat: {}", s),
+            MahjongError::DealerMismatch(s) => {
+                write!(f, "points do not match the dealer status of seat {}", s)
+            }
+            MahjongError::InvalidRuleset(s) => write!(f, "invalid ruleset: {}", s),
+        }
+    }
+}
+
+impl MahjongError {
+    // 和了形だが役がない (ドラのみを含む)
+    pub fn is_yaku_nashi(&self) -> bool {
+        matches!(self, MahjongError::NoYaku | MahjongError::DoraOnly(_))
+    }
+}
+
+impl std::error::Error for MahjongError {}
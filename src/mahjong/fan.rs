@@ -0,0 +1,155 @@
+use super::*;
+
+// 中国麻将, 香港麻雀, 台湾麻雀の番種 (台湾麻雀では台)
+pub struct Fan {
+    pub id: usize,
+    pub name: &'static str, // 番種名
+    pub value: usize,       // 番数 (台湾麻雀では台数)
+}
+
+impl fmt::Debug for Fan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.name, self.value)
+    }
+}
+
+macro_rules! fan {
+    ($id: expr, $n: expr, $v: expr) => {
+        Fan {
+            id: $id,
+            name: $n,
+            value: $v,
+        }
+    };
+}
+pub(crate) use fan;
+
+// 番種表から番種IDの番種を返却
+pub fn fan_by_id(list: &'static [Fan], id: usize) -> Option<&'static Fan> {
+    list.iter().find(|f| f.id == id)
+}
+
+// 番種表の番種 (番種IDは表の1始まりの位置と一致させる)
+pub(crate) fn fan_entry(list: &'static [Fan], id: usize) -> &'static Fan {
+    &list[id - 1]
+}
+
+// 番種IDの番数の合計
+pub(crate) fn fan_sum(list: &'static [Fan], ids: &[usize]) -> usize {
+    ids.iter().map(|&id| fan_entry(list, id).value).sum()
+}
+
+// 番種IDの一覧を番種と成立数にまとめる (番種ID順)
+pub(crate) fn merge_fans(list: &'static [Fan], ids: &[usize]) -> Vec<(&'static Fan, usize)> {
+    let mut result: Vec<(&'static Fan, usize)> = vec![];
+    for &id in ids {
+        match result.iter_mut().find(|(f, _)| f.id == id) {
+            Some((_, n)) => *n += 1,
+            None => result.push((fan_entry(list, id), 1)),
+        }
+    }
+    result.sort_by_key(|(f, _)| f.id);
+    result
+}
+
+// 和了の状況による番種の番種ID (None はその採点モードにない番種)
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SituationFans {
+    pub haitei: Option<usize>,    // 海底撈月 (自摸のみ)
+    pub houtei: Option<usize>,    // 河底撈魚 (ロンのみ)
+    pub rinshan: Option<usize>,   // 槓上開花 (自摸のみ)
+    pub chankan: Option<usize>,   // 搶槓 (ロンのみ)
+    pub tenhou: Option<usize>,    // 天和
+    pub tiihou: Option<usize>,    // 地和
+    pub last_tile: Option<usize>, // 和绝张
+}
+
+// ctx.yaku_flags から成立する和了の状況の番種IDを列挙する
+pub(crate) fn situation_fans(ctx: &HandContext, ids: &SituationFans) -> Vec<usize> {
+    let f = &ctx.yaku_flags;
+    [
+        (ids.haitei, f.haiteiraoyue && ctx.tsumo),
+        (ids.houtei, f.houteiraoyui && !ctx.tsumo),
+        (ids.rinshan, f.rinshankaihou && ctx.tsumo),
+        (ids.chankan, f.chankan && !ctx.tsumo),
+        (ids.tenhou, f.tenhou),
+        (ids.tiihou, f.tiihou),
+        (ids.last_tile, f.last_tile),
+    ]
+    .into_iter()
+    .filter_map(|(id, cond)| id.filter(|_| cond))
+    .collect()
+}
+
+// 花牌の番種の番種ID
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FlowerFans {
+    pub seat: usize,             // 正花 (自風の数字の花牌)
+    pub set: usize,              // 春夏秋冬, 梅蘭菊竹の組が揃った
+    pub set_replaces_seat: bool, // 揃った組の正花は数えない
+}
+
+// 花牌 (春夏秋冬, 梅蘭菊竹の自風の数字が正花) の番種IDを列挙する
+pub(crate) fn flower_fans(ctx: &HandContext, ids: &FlowerFans) -> Vec<usize> {
+    let mut fans = vec![];
+    for set in [FSP..=FWI, FPL..=FBA] {
+        if set.clone().all(|n| ctx.flowers.contains(&Tile(TH, n))) {
+            fans.push(ids.set);
+            if ids.set_replaces_seat {
+                continue;
+            }
+        }
+        for f in ctx.flowers.iter().filter(|f| set.contains(&f.1)) {
+            if f.1 == ctx.jikaze || f.1 == ctx.jikaze + 4 {
+                fans.push(ids.seat);
+            }
+        }
+    }
+    fans
+}
+
+// 待ち牌の種類の数 (アガり牌を各牌に置き換えて is_winning を満たすものを数える)
+// 手牌と鳴きで4枚使っている牌は待ちとしない
+pub(crate) fn wait_count(ctx: &HandContext, is_winning: impl Fn(&HandContext) -> bool) -> usize {
+    let mut used = TileTable::default();
+    let fuuro_tiles = ctx.fuuro.iter().flat_map(|Fuuro(_, v)| v);
+    for TileWithDora(t, _) in ctx.hand_tiles.iter().chain(fuuro_tiles) {
+        used[t.0][t.1] += 1;
+    }
+    tile_kinds()
+        .filter(|&(i, j)| used[i][j] < TILE)
+        .filter(|&(i, j)| {
+            let ctx = HandContext {
+                agari_tile: TileWithDora(Tile(i, j), 0),
+                ..ctx.clone()
+            };
+            is_winning(&ctx)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fan_entry_works_01() {
+        // 番種IDと番種表の位置が一致する
+        for list in [MCR_FAN_LIST, HK_FAN_LIST, TW_TAI_LIST] {
+            for (k, f) in list.iter().enumerate() {
+                assert_eq!(f.id, k + 1);
+                assert!(std::ptr::eq(fan_entry(list, f.id), f));
+            }
+        }
+        assert!(fan_by_id(HK_FAN_LIST, 0).is_none());
+    }
+
+    #[test]
+    fn merge_fans_works_01() {
+        let ids = [HK_SEAT_FLOWER, HK_CONCEALED_HAND, HK_SEAT_FLOWER];
+        let fans = merge_fans(HK_FAN_LIST, &ids);
+        let merged: Vec<(usize, usize)> = fans.iter().map(|(f, n)| (f.id, *n)).collect();
+        assert_eq!(merged, vec![(HK_CONCEALED_HAND, 1), (HK_SEAT_FLOWER, 2)]);
+        assert_eq!(fan_sum(HK_FAN_LIST, &ids), 3);
+    }
+}
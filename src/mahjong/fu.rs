@@ -0,0 +1,95 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+// 符計算のルール設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FuRules {
+    pub double_wind_pair_fu: usize, // 場風かつ自風の雀頭 (連風牌) の符 (2 or 4)
+    pub rinshan_tsumo_fu: bool,     // 嶺上開花にツモ符を付けるか
+}
+
+impl Default for FuRules {
+    fn default() -> Self {
+        Self {
+            double_wind_pair_fu: 2,
+            rinshan_tsumo_fu: true,
+        }
+    }
+}
+
+// 符の内訳の項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuItem {
+    Futei,                  // 副底
+    MenzenRon,              // 門前加符
+    Tsumo,                  // ツモ符
+    Block(BlockType, Tile), // 面子 (刻子, 槓子)
+    Pair(Tile),             // 雀頭
+    Wait(WaitType),         // 待ちの形
+    PinfuTsumo,             // 例外: 平和ツモは20符
+    OpenPinfu,              // 例外: 喰い平和形は30符
+    SevenPair,              // 例外: 七対子は25符
+}
+
+impl fmt::Display for FuItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FuItem::Futei => write!(f, "副底"),
+            FuItem::MenzenRon => write!(f, "門前加符"),
+            FuItem::Tsumo => write!(f, "ツモ"),
+            FuItem::Block(bt, t) => {
+                let name = match bt {
+                    BlockType::Koutsu => "暗刻",
+                    BlockType::Pon => "明刻",
+                    BlockType::Minkan => "明槓",
+                    BlockType::Ankan => "暗槓",
+                    _ => "面子",
+                };
+                write!(f, "{} {}", name, t)
+            }
+            FuItem::Pair(t) => write!(f, "雀頭 {}", t),
+            FuItem::Wait(w) => write!(f, "{}待ち", w),
+            FuItem::PinfuTsumo => write!(f, "平和ツモ"),
+            FuItem::OpenPinfu => write!(f, "喰い平和"),
+            FuItem::SevenPair => write!(f, "七対子"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuBreakdown {
+    pub items: Vec<(FuItem, usize)>, // (項目, 符)
+    pub subtotal: usize,             // 項目の合計 (切り上げ前)
+    pub total: usize,                // 切り上げ, 例外の適用後
+}
+
+impl FuBreakdown {
+    // 項目を合計して1の位を切り上げる (七対子の25符は切り上げない)
+    pub fn new(items: Vec<(FuItem, usize)>) -> Self {
+        let subtotal = items.iter().map(|(_, fu)| fu).sum::<usize>();
+        let total = if items.iter().any(|(item, _)| *item == FuItem::SevenPair) {
+            subtotal
+        } else {
+            subtotal.div_ceil(10) * 10
+        };
+        Self {
+            items,
+            subtotal,
+            total,
+        }
+    }
+}
+
+impl fmt::Display for FuBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (item, fu) in &self.items {
+            writeln!(f, "{}: {}符", item, fu)?;
+        }
+        write!(f, "合計: {}符", self.subtotal)?;
+        if self.subtotal != self.total {
+            write!(f, " → {}符", self.total)?;
+        }
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use super::*;
 pub type TileRow = [usize; TNUM];
 pub type TileTable = [TileRow; TYPE]; // 各牌の枚数の二次元配列
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HandContext {
     pub hand_tiles: Vec<TileWithDora>, // 元々の手牌 (鳴き、アガり牌は含まない)
     pub fuuro: Vec<Fuuro>,             // 鳴いている面子
@@ -14,10 +14,15 @@ pub struct HandContext {
     pub dora: Vec<Tile>,               // ドラ
     pub ura_dora: Vec<Tile>,           // 裏ドラ
     pub nuki_dora: Vec<TileWithDora>,  // 抜きドラ
+    pub flowers: Vec<Tile>,            // 花牌
     pub yaku_flags: YakuFlags,         // 組み合わせ以外による役 外部から設定を行う
+    pub honba: usize,                  // 本場 (new では0)
+    pub dealer_streak: usize,          // 莊家の連莊の回数 (new では0, 台湾麻雀の連莊の台)
+    pub mentsu: usize,                 // 和了形の面子の数 (new では4, 台湾麻雀の16枚は5)
 }
 
 impl HandContext {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         hand_tiles: Vec<TileWithDora>,
         fuuro: Vec<Fuuro>,
@@ -28,6 +33,7 @@ impl HandContext {
         dora: Vec<Tile>,
         ura_dora: Vec<Tile>,
         nuki_dora: Vec<TileWithDora>,
+        flowers: Vec<Tile>,
         yaku_flags: YakuFlags,
     ) -> Self {
         Self {
@@ -40,8 +46,213 @@ impl HandContext {
             dora,
             ura_dora,
             nuki_dora,
+            flowers,
             yaku_flags,
+            honba: 0,
+            dealer_streak: 0,
+            mentsu: 4,
+        }
+    }
+
+    // parse_hand の結果から作成 (花牌も設定, ドラ・役フラグは空)
+    pub fn from_parsed(
+        hand: ParsedHand,
+        tsumo: bool,
+        bakaze: Tnum,
+        jikaze: Tnum,
+    ) -> Result<Self, MahjongError> {
+        let Some(agari_tile) = hand.agari_tile else {
+            let cnt = hand.hand_tiles.len() + 3 * hand.fuuro.len();
+            return Err(MahjongError::InvalidTileCount(cnt));
+        };
+        Ok(Self {
+            flowers: hand.flowers,
+            ..Self::new(
+                hand.hand_tiles,
+                hand.fuuro,
+                agari_tile,
+                tsumo,
+                bakaze,
+                jikaze,
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                YakuFlags::default(),
+            )
+        })
+    }
+
+    // ドラ表示牌, 裏ドラ表示牌からドラ, 裏ドラを設定
+    pub fn set_dora_indicators(
+        &mut self,
+        dora_indicators: &[Tile],
+        ura_dora_indicators: &[Tile],
+        rules: &Ruleset,
+    ) {
+        let sanma = rules.sanma.is_some();
+        self.dora = dora_indicators
+            .iter()
+            .map(|t| indicator_to_dora(t, sanma))
+            .collect();
+        self.ura_dora = ura_dora_indicators
+            .iter()
+            .map(|t| indicator_to_dora(t, sanma))
+            .collect();
+    }
+
+    // 和了形の面子の数が採点モードで扱う数と一致するかをチェック
+    pub fn check_mentsu(&self, mentsu: usize) -> Result<(), MahjongError> {
+        if self.mentsu != mentsu {
+            return Err(MahjongError::InvalidMentsuCount(self.mentsu));
+        }
+        Ok(())
+    }
+
+    // 副露の形, 牌の枚数 (鳴き・アガり牌を含めて 3 * mentsu + 2 枚), 同種の牌の枚数をチェック
+    pub fn check_tiles(&self) -> Result<(), MahjongError> {
+        for fuuro in &self.fuuro {
+            if !fuuro.valid() {
+                return Err(MahjongError::InvalidMeld(fuuro.to_string()));
+            }
         }
+
+        let cnt = self.hand_tiles.len() + 3 * self.fuuro.len() + 1;
+        if cnt != 3 * self.mentsu + 2 {
+            return Err(MahjongError::InvalidTileCount(cnt));
+        }
+
+        let mut table = TileTable::default();
+        let fuuro_tiles = self.fuuro.iter().flat_map(|Fuuro(_, v)| v);
+        for TileWithDora(t, _) in self.hand_tiles.iter().chain(fuuro_tiles) {
+            table[t.0][t.1] += 1;
+        }
+        let TileWithDora(at, _) = self.agari_tile;
+        table[at.0][at.1] += 1;
+        for (k, t) in self.flowers.iter().enumerate() {
+            if !t.is_flower() {
+                return Err(MahjongError::InvalidTileSymbol(t.to_string()));
+            }
+            // 花牌は各1枚
+            if self.flowers[..k].contains(t) {
+                return Err(MahjongError::DuplicateFlower(*t));
+            }
+        }
+        for (i, row) in table.iter().enumerate() {
+            for (j, &n) in row.iter().enumerate().skip(1) {
+                if n > TILE {
+                    return Err(MahjongError::TooManyCopies(Tile(i, j)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // 暗槓以外の鳴きの有無
+    pub fn is_open(&self) -> bool {
+        self.fuuro
+            .iter()
+            .any(|Fuuro(ft, _)| !matches!(ft, FuuroType::Ankan))
+    }
+
+    // 特殊条件の役の設定と手牌・和了り方の矛盾をすべて返却
+    pub fn validate_flags(&self) -> Vec<FlagIssue> {
+        let f = &self.yaku_flags;
+        let is_open = self.is_open();
+        let is_dealer = self.jikaze == WEA;
+        let mut issues = vec![];
+        let mut check = |cond: bool, issue: FlagIssue| {
+            if cond {
+                issues.push(issue);
+            }
+        };
+
+        check(f.menzentsumo && is_open, FlagIssue::MenzentsumoOnOpenHand);
+        check(f.menzentsumo && !self.tsumo, FlagIssue::MenzentsumoOnRon);
+        check(
+            (f.riichi || f.double_riichi) && is_open,
+            FlagIssue::RiichiOnOpenHand,
+        );
+        check(
+            f.ippatsu && !(f.riichi || f.double_riichi),
+            FlagIssue::IppatsuWithoutRiichi,
+        );
+        check(
+            f.open_riichi && !(f.riichi || f.double_riichi),
+            FlagIssue::OpenRiichiWithoutRiichi,
+        );
+        check(f.haiteiraoyue && !self.tsumo, FlagIssue::HaiteiOnRon);
+        check(f.houteiraoyui && self.tsumo, FlagIssue::HouteiOnTsumo);
+        check(f.rinshankaihou && !self.tsumo, FlagIssue::RinshanOnRon);
+        check(
+            f.haiteiraoyue && f.rinshankaihou,
+            FlagIssue::HaiteiWithRinshan,
+        );
+        check(f.chankan && self.tsumo, FlagIssue::ChankanOnTsumo);
+        check(f.tenhou && !is_dealer, FlagIssue::TenhouForNonDealer);
+        check(f.tiihou && is_dealer, FlagIssue::TiihouForDealer);
+        check(f.renhou && is_dealer, FlagIssue::RenhouForDealer);
+        check(
+            (f.tenhou || f.tiihou) && !self.tsumo,
+            FlagIssue::TenhouOrTiihouOnRon,
+        );
+        check(f.renhou && self.tsumo, FlagIssue::RenhouOnTsumo);
+        check(
+            (f.tenhou || f.tiihou || f.renhou) && !self.fuuro.is_empty(),
+            FlagIssue::FirstTurnWinWithMeld,
+        );
+        check(
+            (f.tsubamegaeshi || f.kanburi || f.open_riichi_non_riichi_loser) && self.tsumo,
+            FlagIssue::RonOnlyFlagOnTsumo,
+        );
+        issues
+    }
+}
+
+// 特殊条件の役の設定の矛盾
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagIssue {
+    MenzentsumoOnOpenHand,   // 鳴いている手での門前清自摸和
+    MenzentsumoOnRon,        // ロン和了での門前清自摸和
+    RiichiOnOpenHand,        // 鳴いている手での立直
+    IppatsuWithoutRiichi,    // 立直なしの一発
+    OpenRiichiWithoutRiichi, // 立直なしのオープン立直
+    HaiteiOnRon,             // ロン和了での海底摸月
+    HouteiOnTsumo,           // ツモ和了での河底撈魚
+    RinshanOnRon,            // ロン和了での嶺上開花
+    HaiteiWithRinshan,       // 海底摸月と嶺上開花の複合
+    ChankanOnTsumo,          // ツモ和了での槍槓
+    TenhouForNonDealer,      // 子の天和
+    TiihouForDealer,         // 親の地和
+    RenhouForDealer,         // 親の人和
+    TenhouOrTiihouOnRon,     // ロン和了での天和・地和
+    RenhouOnTsumo,           // ツモ和了での人和
+    FirstTurnWinWithMeld,    // 鳴きがある手での天和・地和・人和
+    RonOnlyFlagOnTsumo,      // ツモ和了での燕返し・槓振り・オープン立直放銃
+}
+
+impl fmt::Display for FlagIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            FlagIssue::MenzentsumoOnOpenHand => "menzentsumo on an open hand",
+            FlagIssue::MenzentsumoOnRon => "menzentsumo on ron",
+            FlagIssue::RiichiOnOpenHand => "riichi on an open hand",
+            FlagIssue::IppatsuWithoutRiichi => "ippatsu without riichi",
+            FlagIssue::OpenRiichiWithoutRiichi => "open riichi without riichi",
+            FlagIssue::HaiteiOnRon => "haiteiraoyue on ron",
+            FlagIssue::HouteiOnTsumo => "houteiraoyui on tsumo",
+            FlagIssue::RinshanOnRon => "rinshankaihou on ron",
+            FlagIssue::HaiteiWithRinshan => "haiteiraoyue with rinshankaihou",
+            FlagIssue::ChankanOnTsumo => "chankan on tsumo",
+            FlagIssue::TenhouForNonDealer => "tenhou for a non-dealer",
+            FlagIssue::TiihouForDealer => "tiihou for the dealer",
+            FlagIssue::RenhouForDealer => "renhou for the dealer",
+            FlagIssue::TenhouOrTiihouOnRon => "tenhou or tiihou on ron",
+            FlagIssue::RenhouOnTsumo => "renhou on tsumo",
+            FlagIssue::FirstTurnWinWithMeld => "first-turn win with a meld",
+            FlagIssue::RonOnlyFlagOnTsumo => "ron-only flag on tsumo",
+        };
+        write!(f, "{}", s)
     }
 }
 
@@ -59,6 +270,10 @@ pub struct Fuuro(pub FuuroType, pub Vec<TileWithDora>);
 impl Fuuro {
     pub fn valid(&self) -> bool {
         let Fuuro(ft, v) = &self;
+        // オールマイティは鳴きに含めない (手牌とアガり牌のみ)
+        if v.iter().any(|TileWithDora(t, _)| t.is_almighty()) {
+            return false;
+        }
         match ft {
             FuuroType::Chi => {
                 if v.len() != 3 {
@@ -100,6 +315,144 @@ impl Fuuro {
     }
 }
 
+// "123m406p789s11z+5p (pon 777z) [kan 1111s]" 形式の手牌
+// 鳴きは (chi ...), (pon ...), (kan ...) で明槓, [kan ...] で暗槓を表す
+#[derive(Debug, Clone)]
+pub struct ParsedHand {
+    pub hand_tiles: Vec<TileWithDora>, // 手牌 (鳴き、アガり牌は含まない)
+    pub fuuro: Vec<Fuuro>,             // 鳴いている面子
+    pub agari_tile: Option<TileWithDora>, // アガり牌 (+ の後ろ, 省略可)
+    pub flowers: Vec<Tile>,            // 花牌 (h は位置によらず花牌として扱う)
+}
+
+fn parse_fuuro(s: &str, closed: bool) -> Result<Fuuro, MahjongError> {
+    let invalid = || MahjongError::InvalidMeld(s.to_string());
+    let mut it = s.split_whitespace();
+    let (kind, tiles) = match (it.next(), it.next(), it.next()) {
+        (Some(k), Some(t), None) => (k, parse_tiles(t)?),
+        _ => return Err(invalid()),
+    };
+    let ft = match (kind, closed) {
+        ("chi", false) => FuuroType::Chi,
+        ("pon", false) => FuuroType::Pon,
+        ("kan", false) => FuuroType::Minkan,
+        ("kan", true) => FuuroType::Ankan,
+        _ => return Err(invalid()),
+    };
+    let fuuro = Fuuro(ft, tiles);
+    if !fuuro.valid() {
+        return Err(invalid());
+    }
+    Ok(fuuro)
+}
+
+pub fn parse_hand(s: &str) -> Result<ParsedHand, MahjongError> {
+    let mut hand_tiles = Vec::new();
+    let mut fuuro = Vec::new();
+    let mut agari_tile = None;
+    let mut flowers = Vec::new();
+
+    let mut rest = s.trim_start();
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '(' | '[' => {
+                let close = if c == '(' { ')' } else { ']' };
+                let end = rest
+                    .find(close)
+                    .ok_or_else(|| MahjongError::InvalidMeld(rest.to_string()))?;
+                fuuro.push(parse_fuuro(&rest[1..end], c == '[')?);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                let is_agari = c == '+';
+                let body = if is_agari { &rest[1..] } else { rest };
+                let end = body
+                    .find(|c: char| c.is_whitespace() || "([+".contains(c))
+                    .unwrap_or(body.len());
+                let tiles = parse_tiles(&body[..end])?;
+                if is_agari {
+                    if tiles.len() != 1 || agari_tile.is_some() {
+                        return Err(MahjongError::InvalidTileSymbol(rest.to_string()));
+                    }
+                    agari_tile = Some(tiles[0]);
+                } else {
+                    for tile in tiles {
+                        if tile.0.is_flower() {
+                            flowers.push(tile.0);
+                        } else {
+                            hand_tiles.push(tile);
+                        }
+                    }
+                }
+                rest = &body[end..];
+            }
+        }
+        rest = rest.trim_start();
+    }
+
+    Ok(ParsedHand {
+        hand_tiles,
+        fuuro,
+        agari_tile,
+        flowers,
+    })
+}
+
+impl fmt::Display for ParsedHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_tiles(&self.hand_tiles))?;
+        if let Some(t) = self.agari_tile {
+            write!(f, "+{}", format_tiles(&[t]))?;
+        }
+        if !self.flowers.is_empty() {
+            let flowers: Vec<TileWithDora> =
+                self.flowers.iter().map(|&t| TileWithDora(t, 0)).collect();
+            write!(f, " {}", format_tiles(&flowers))?;
+        }
+        for fuuro in &self.fuuro {
+            write!(f, " {}", fuuro)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for ParsedHand {
+    type Err = MahjongError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hand(s)
+    }
+}
+
+// テスト用: MPSZ 形式の手牌から場風東の HandContext を作成
+#[cfg(test)]
+pub(crate) fn test_ctx(s: &str, tsumo: bool, jikaze: Tnum) -> HandContext {
+    HandContext::from_parsed(parse_hand(s).unwrap(), tsumo, WEA, jikaze).unwrap()
+}
+
+// テスト用: MPSZ 形式の牌を各牌の枚数の表にする
+#[cfg(test)]
+pub(crate) fn to_table(s: &str) -> TileTable {
+    let mut table = TileTable::default();
+    for TileWithDora(t, _) in parse_tiles(s).unwrap() {
+        table[t.0][t.1] += 1;
+    }
+    table
+}
+
+impl fmt::Display for Fuuro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Fuuro(ft, v) = self;
+        let tiles = format_tiles(v);
+        match ft {
+            FuuroType::Chi => write!(f, "(chi {})", tiles),
+            FuuroType::Pon => write!(f, "(pon {})", tiles),
+            FuuroType::Minkan => write!(f, "(kan {})", tiles),
+            FuuroType::Ankan => write!(f, "[kan {}]", tiles),
+        }
+    }
+}
+
 // 特殊形&特殊条件の役
 #[derive(Debug, Default, Clone, Copy)]
 pub struct YakuFlags {
@@ -113,6 +466,13 @@ pub struct YakuFlags {
     pub chankan: bool,
     pub tenhou: bool,
     pub tiihou: bool,
+    pub renhou: bool,                       // 人和
+    pub nagashi_mangan: bool,               // 流し満貫 (和了形によらず満貫として扱う)
+    pub open_riichi: bool,                  // オープン立直 (riichi と合わせて設定する)
+    pub open_riichi_non_riichi_loser: bool, // オープン立直にリーチしていない他家が放銃した
+    pub tsubamegaeshi: bool,                // 燕返し (リーチ宣言牌でのロン)
+    pub kanburi: bool,                      // 槓振り (槓の後の打牌でのロン)
+    pub last_tile: bool,                    // 和绝张 (場に3枚見えている牌での和了, 中国麻将)
 }
 
 #[cfg(test)]
@@ -218,4 +578,179 @@ mod tests {
         let fuuro = Fuuro(FuuroType::Pon, vec![tile1, tile2, tile3]);
         assert!(!fuuro.valid());
     }
+
+    #[test]
+    fn parse_hand_works_01() {
+        let hand = parse_hand("123m406p789s11z+5p (pon 777z) [kan 1111s]").unwrap();
+        assert_eq!(hand.hand_tiles.len(), 11);
+        assert_eq!(hand.fuuro.len(), 2);
+        assert!(matches!(hand.fuuro[0].0, FuuroType::Pon));
+        assert!(matches!(hand.fuuro[1].0, FuuroType::Ankan));
+        assert_eq!(hand.agari_tile, Some(TileWithDora(Tile(TP, 5), 0)));
+    }
+
+    #[test]
+    fn parse_hand_works_02() {
+        let hand = parse_hand("1112345678999m").unwrap();
+        assert_eq!(hand.hand_tiles.len(), 13);
+        assert!(hand.fuuro.is_empty());
+        assert_eq!(hand.agari_tile, None);
+    }
+
+    #[test]
+    fn parse_hand_works_03() {
+        let err = |s: &str| parse_hand(s).unwrap_err();
+        assert_eq!(
+            err("123m (pon 123m)"),
+            MahjongError::InvalidMeld("pon 123m".to_string())
+        );
+        assert_eq!(
+            err("123m [chi 123m]"),
+            MahjongError::InvalidMeld("chi 123m".to_string())
+        );
+        assert_eq!(
+            err("123m (pon 777z"),
+            MahjongError::InvalidMeld("(pon 777z".to_string())
+        );
+        assert_eq!(
+            err("123m+56p"),
+            MahjongError::InvalidTileSymbol("+56p".to_string())
+        );
+    }
+
+    #[test]
+    fn format_hand_works_01() {
+        let s = "123m406p789s11z+5p (pon 777z) [kan 1111s]";
+        assert_eq!(parse_hand(s).unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn format_hand_works_02() {
+        let s = "23m406p789s11z+0m (chi 312s)";
+        assert_eq!(parse_hand(s).unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn parse_hand_works_04() {
+        let hand: ParsedHand = "11122233344455m".parse().unwrap();
+        assert_eq!(hand.hand_tiles.len(), 14);
+    }
+
+    #[test]
+    fn from_parsed_works_01() {
+        let ctx = test_ctx("123m406p789s11z+5p 15h (pon 777z)", true, WSO);
+        assert_eq!(ctx.agari_tile, TileWithDora(Tile(TP, 5), 0));
+        assert_eq!(ctx.flowers, vec![Tile(TH, FSP), Tile(TH, FPL)]);
+        assert_eq!((ctx.bakaze, ctx.jikaze), (WEA, WSO));
+
+        // アガり牌の省略
+        let hand = parse_hand("123m406p789s11z (pon 777z)").unwrap();
+        assert_eq!(
+            HandContext::from_parsed(hand, false, WEA, WEA).unwrap_err(),
+            MahjongError::InvalidTileCount(14)
+        );
+    }
+
+    #[test]
+    fn check_tiles_works_01() {
+        let ctx = test_ctx("1111234567899m+1m", false, WEA);
+        assert_eq!(
+            ctx.check_tiles(),
+            Err(MahjongError::TooManyCopies(Tile(TM, 1)))
+        );
+    }
+
+    #[test]
+    fn check_tiles_works_02() {
+        let ctx = test_ctx("123456789m+1m (pon 777z)", false, WEA);
+        assert_eq!(ctx.check_tiles(), Err(MahjongError::InvalidTileCount(13)));
+    }
+
+    #[test]
+    fn check_tiles_works_03() {
+        // 同じ花牌が2枚
+        let mut ctx = test_ctx("123456789m11z23s+4s 122h", false, WEA);
+        assert_eq!(
+            ctx.check_tiles(),
+            Err(MahjongError::DuplicateFlower(Tile(TH, FSU)))
+        );
+        ctx.flowers.pop();
+        assert_eq!(ctx.check_tiles(), Ok(()));
+    }
+
+    #[test]
+    fn parse_hand_works_05() {
+        let s = "123m406p789s11z+5p 15h (pon 777z)";
+        let hand = parse_hand(s).unwrap();
+        assert_eq!(hand.hand_tiles.len(), 11);
+        assert_eq!(hand.flowers, vec![Tile(TH, FSP), Tile(TH, FPL)]);
+        assert_eq!(hand.to_string(), s);
+    }
+
+    fn flags_ctx(s: &str, tsumo: bool, jikaze: Tnum, yaku_flags: YakuFlags) -> HandContext {
+        HandContext {
+            yaku_flags,
+            ..test_ctx(s, tsumo, jikaze)
+        }
+    }
+
+    #[test]
+    fn validate_flags_works_01() {
+        // 鳴いている手のロン和了での門前清自摸和, 立直なしの一発
+        let ctx = flags_ctx(
+            "123m456p11z78s+9s (pon 555z)",
+            false,
+            WSO,
+            YakuFlags {
+                menzentsumo: true,
+                ippatsu: true,
+                ..YakuFlags::default()
+            },
+        );
+        assert_eq!(
+            ctx.validate_flags(),
+            vec![
+                FlagIssue::MenzentsumoOnOpenHand,
+                FlagIssue::MenzentsumoOnRon,
+                FlagIssue::IppatsuWithoutRiichi,
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_flags_works_02() {
+        // 海底摸月と嶺上開花, 子の天和
+        let ctx = flags_ctx(
+            "123m456p789s11z78s+9s",
+            true,
+            WSO,
+            YakuFlags {
+                haiteiraoyue: true,
+                rinshankaihou: true,
+                tenhou: true,
+                ..YakuFlags::default()
+            },
+        );
+        assert_eq!(
+            ctx.validate_flags(),
+            vec![FlagIssue::HaiteiWithRinshan, FlagIssue::TenhouForNonDealer]
+        );
+    }
+
+    #[test]
+    fn validate_flags_works_03() {
+        // 暗槓のみの立直ツモは問題なし
+        let ctx = flags_ctx(
+            "123m456p11z78s+9s [kan 5555z]",
+            true,
+            WEA,
+            YakuFlags {
+                menzentsumo: true,
+                riichi: true,
+                ippatsu: true,
+                ..YakuFlags::default()
+            },
+        );
+        assert!(ctx.validate_flags().is_empty());
+    }
 }
@@ -0,0 +1,415 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+// 満貫 (上限の番数として扱う) の番数
+pub const HK_LIMIT: usize = 13;
+
+// 香港麻雀の番種ID
+pub const HK_SELF_DRAWN: usize = 1;
+pub const HK_CONCEALED_HAND: usize = 2;
+pub const HK_NO_FLOWERS: usize = 3;
+pub const HK_SEAT_FLOWER: usize = 4;
+pub const HK_FLOWER_SET: usize = 5;
+pub const HK_DRAGON_PUNG: usize = 6;
+pub const HK_SEAT_WIND: usize = 7;
+pub const HK_PREVALENT_WIND: usize = 8;
+pub const HK_ALL_CHOWS: usize = 9;
+pub const HK_LAST_TILE_DRAW: usize = 10;
+pub const HK_OUT_WITH_REPLACEMENT_TILE: usize = 11;
+pub const HK_ROBBING_THE_KONG: usize = 12;
+pub const HK_ALL_TERMINALS_AND_HONORS: usize = 13;
+pub const HK_ALL_PUNGS: usize = 14;
+pub const HK_HALF_FLUSH: usize = 15;
+pub const HK_LITTLE_THREE_DRAGONS: usize = 16;
+pub const HK_FULL_FLUSH: usize = 17;
+pub const HK_BIG_THREE_DRAGONS: usize = 18;
+pub const HK_LITTLE_FOUR_WINDS: usize = 19;
+pub const HK_BIG_FOUR_WINDS: usize = 20;
+pub const HK_THIRTEEN_ORPHANS: usize = 21;
+pub const HK_ALL_HONORS: usize = 22;
+pub const HK_ALL_TERMINALS: usize = 23;
+pub const HK_FOUR_CONCEALED_PUNGS: usize = 24;
+pub const HK_FOUR_KONGS: usize = 25;
+pub const HK_NINE_GATES: usize = 26;
+pub const HK_HEAVENLY_HAND: usize = 27;
+pub const HK_EARTHLY_HAND: usize = 28;
+
+// 香港麻雀 (古典ルール) の番種 (番種ID順, HK_LIMIT 以上は満貫)
+pub const HK_FAN_LIST: &[Fan] = &[
+    fan!(HK_SELF_DRAWN, "自摸", 1),
+    fan!(HK_CONCEALED_HAND, "門前清", 1),
+    fan!(HK_NO_FLOWERS, "無花", 1),
+    fan!(HK_SEAT_FLOWER, "正花", 1),
+    fan!(HK_FLOWER_SET, "一台花", 2),
+    fan!(HK_DRAGON_PUNG, "番子", 1),
+    fan!(HK_SEAT_WIND, "門風", 1),
+    fan!(HK_PREVALENT_WIND, "圈風", 1),
+    fan!(HK_ALL_CHOWS, "平糊", 1),
+    fan!(HK_LAST_TILE_DRAW, "海底撈月", 1),
+    fan!(HK_OUT_WITH_REPLACEMENT_TILE, "槓上開花", 1),
+    fan!(HK_ROBBING_THE_KONG, "搶槓", 1),
+    fan!(HK_ALL_TERMINALS_AND_HONORS, "混么九", 1),
+    fan!(HK_ALL_PUNGS, "對對糊", 3),
+    fan!(HK_HALF_FLUSH, "混一色", 3),
+    fan!(HK_LITTLE_THREE_DRAGONS, "小三元", 5),
+    fan!(HK_FULL_FLUSH, "清一色", 7),
+    fan!(HK_BIG_THREE_DRAGONS, "大三元", 8),
+    fan!(HK_LITTLE_FOUR_WINDS, "小四喜", 10),
+    // 満貫
+    fan!(HK_BIG_FOUR_WINDS, "大四喜", HK_LIMIT),
+    fan!(HK_THIRTEEN_ORPHANS, "十三么", HK_LIMIT),
+    fan!(HK_ALL_HONORS, "字一色", HK_LIMIT),
+    fan!(HK_ALL_TERMINALS, "清么九", HK_LIMIT),
+    fan!(HK_FOUR_CONCEALED_PUNGS, "坎坎糊", HK_LIMIT),
+    fan!(HK_FOUR_KONGS, "十八羅漢", HK_LIMIT),
+    fan!(HK_NINE_GATES, "九子連環", HK_LIMIT),
+    fan!(HK_HEAVENLY_HAND, "天糊", HK_LIMIT),
+    fan!(HK_EARTHLY_HAND, "地糊", HK_LIMIT),
+];
+
+// 牌姿の判定を役と共有する番種 (番種ID, 判定)
+const HK_PATTERNS: &[(usize, YakuFunc)] = &[
+    (HK_ALL_TERMINALS_AND_HONORS, is_honroutou),
+    (HK_ALL_PUNGS, is_toitoihou),
+    (HK_HALF_FLUSH, is_honitsu),
+    (HK_LITTLE_THREE_DRAGONS, is_shousangen),
+    (HK_FULL_FLUSH, is_chinitsu),
+    (HK_BIG_THREE_DRAGONS, is_daisangen),
+    (HK_LITTLE_FOUR_WINDS, is_shousuushii),
+    (HK_BIG_FOUR_WINDS, is_daisuushii),
+    (HK_THIRTEEN_ORPHANS, |c| {
+        is_kokushimusou(c) || is_kokushimusoujuusanmenmachi(c)
+    }),
+    (HK_ALL_HONORS, is_tsuuiisou),
+    (HK_ALL_TERMINALS, is_chinroutou),
+    (HK_FOUR_CONCEALED_PUNGS, |c| {
+        is_suuankou(c) || is_suuankoutanki(c)
+    }),
+    (HK_FOUR_KONGS, is_suukantsu),
+    (HK_NINE_GATES, |c| {
+        is_chuurenpoutou(c) || is_junseichuurenpoutou(c)
+    }),
+];
+
+// 和了の状況による番種
+const HK_SITUATION_FANS: SituationFans = SituationFans {
+    haitei: Some(HK_LAST_TILE_DRAW),
+    houtei: None,
+    rinshan: Some(HK_OUT_WITH_REPLACEMENT_TILE),
+    chankan: Some(HK_ROBBING_THE_KONG),
+    tenhou: Some(HK_HEAVENLY_HAND),
+    tiihou: Some(HK_EARTHLY_HAND),
+    last_tile: None,
+};
+
+// 花牌の番種 (一台花は同じ組の正花を置き換える)
+const HK_FLOWER_FANS: FlowerFans = FlowerFans {
+    seat: HK_SEAT_FLOWER,
+    set: HK_FLOWER_SET,
+    set_replaces_seat: true,
+};
+
+// 香港麻雀のルール設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HkRules {
+    pub min_faan: usize,    // 和了に必要な番数 (起糊)
+    pub max_faan: usize,    // 番数の上限 (満貫)
+    pub base_point: Point,  // 0番の支払い
+    pub flowers: bool,      // 花牌 (無花, 正花, 一台花) を数える
+    pub full_shooter: bool, // 全銃 (放銃者が3人分を支払う, false の場合は半銃)
+}
+
+impl Default for HkRules {
+    fn default() -> Self {
+        Self {
+            min_faan: 3,
+            max_faan: HK_LIMIT,
+            base_point: 1,
+            flowers: true,
+            full_shooter: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HkResult {
+    pub fans: Vec<(&'static Fan, usize)>, // 番種と成立数
+    pub faan: usize,                      // 番数 (上限を適用済み)
+    pub limit: bool,                      // 満貫 (上限に達した)
+    pub unit: Point,                      // 放銃者以外の1人あたりの支払い
+}
+
+// 和了形の解釈のうち最も番数の多いものを返却
+pub fn score_hk(ctx: &HandContext, rules: &HkRules) -> Result<HkResult, MahjongError> {
+    ctx.check_mentsu(4)?;
+    ctx.check_tiles()?;
+
+    let mut best: Option<(usize, Vec<usize>)> = None;
+    for yaku_ctx in detect_winning(ctx) {
+        // 七対子は和了形として認めない
+        if matches!(yaku_ctx.form(), YakuForm::SevenPair(_)) {
+            continue;
+        }
+        let fans = count_hk_fans(ctx, &yaku_ctx, rules);
+        let faan = fan_sum(HK_FAN_LIST, &fans);
+        if best.as_ref().is_none_or(|(b, _)| faan > *b) {
+            best = Some((faan, fans));
+        }
+    }
+    let Some((faan, fans)) = best else {
+        return Err(MahjongError::NotWinning);
+    };
+
+    let limit = faan >= rules.max_faan;
+    let faan = faan.min(rules.max_faan);
+    if faan < rules.min_faan {
+        return Err(MahjongError::InsufficientFan(rules.min_faan, faan));
+    }
+
+    Ok(HkResult {
+        fans: merge_fans(HK_FAN_LIST, &fans),
+        faan,
+        limit,
+        unit: get_unit_points_hk(faan, rules),
+    })
+}
+
+// 1番ごとに倍になる支払いの単位 (base_point * 2^番数)
+pub fn get_unit_points_hk(faan: usize, rules: &HkRules) -> Point {
+    let faan = faan.min(rules.max_faan).min(30) as u32;
+    rules.base_point.saturating_mul(1 << faan)
+}
+
+// 各座席の点数の増減を返却 (loser が None の場合は自摸和了)
+// 放銃者は単位の2倍 (全銃の場合は3人分の4倍), 他家は単位, 自摸和了は全員が単位の2倍を支払う
+pub fn get_payments_hk(
+    faan: usize,
+    rules: &HkRules,
+    winner: Seat,
+    loser: Option<Seat>,
+) -> Result<Vec<Point>, MahjongError> {
+    check_seats(SEAT, winner, loser)?;
+
+    let unit = get_unit_points_hk(faan, rules);
+    let mut deltas = vec![0; SEAT];
+    for seat in 0..SEAT {
+        if seat == winner {
+            continue;
+        }
+        let p = match loser {
+            None => 2 * unit,
+            Some(loser) if rules.full_shooter => {
+                if loser == seat {
+                    4 * unit
+                } else {
+                    0
+                }
+            }
+            Some(loser) if loser == seat => 2 * unit,
+            Some(_) => unit,
+        };
+        deltas[seat] -= p;
+        deltas[winner] += p;
+    }
+    Ok(deltas)
+}
+
+// 成立する番種IDを列挙する
+fn count_hk_fans(ctx: &HandContext, yaku_ctx: &YakuContext, rules: &HkRules) -> Vec<usize> {
+    let mut fans = vec![];
+    for &(id, pattern) in HK_PATTERNS {
+        if pattern(yaku_ctx) {
+            fans.push(id);
+        }
+    }
+    fans.extend(situation_fans(ctx, &HK_SITUATION_FANS));
+    // 満貫の番種が含まれている場合, 満貫の番種のみを返却
+    if fans
+        .iter()
+        .any(|&id| fan_entry(HK_FAN_LIST, id).value >= HK_LIMIT)
+    {
+        fans.retain(|&id| fan_entry(HK_FAN_LIST, id).value >= HK_LIMIT);
+        return fans;
+    }
+
+    let blocks = match yaku_ctx.form() {
+        YakuForm::FiveBlock(fb) => fb.blocks(),
+        _ => &[],
+    };
+    let mut all_chows = !blocks.is_empty();
+    for &Block(bt, t) in blocks {
+        match bt {
+            BlockType::Pair => {
+                if t.is_dragon() || (t.is_wind() && (t.1 == ctx.bakaze || t.1 == ctx.jikaze)) {
+                    all_chows = false;
+                }
+            }
+            BlockType::Shuntsu | BlockType::Chi => {}
+            _ => {
+                all_chows = false;
+                if t.is_dragon()
+                    && !fans.contains(&HK_LITTLE_THREE_DRAGONS)
+                    && !fans.contains(&HK_BIG_THREE_DRAGONS)
+                {
+                    fans.push(HK_DRAGON_PUNG);
+                }
+                if t.is_wind() && t.1 == ctx.jikaze {
+                    fans.push(HK_SEAT_WIND);
+                }
+                if t.is_wind() && t.1 == ctx.bakaze {
+                    fans.push(HK_PREVALENT_WIND);
+                }
+            }
+        }
+    }
+    if all_chows {
+        fans.push(HK_ALL_CHOWS);
+    }
+    if !yaku_ctx.is_open() {
+        fans.push(HK_CONCEALED_HAND);
+    }
+    if ctx.tsumo {
+        fans.push(HK_SELF_DRAWN);
+    }
+
+    // 花牌 (無花, 正花, 一台花)
+    if rules.flowers {
+        if ctx.flowers.is_empty() {
+            fans.push(HK_NO_FLOWERS);
+        }
+        fans.extend(flower_fans(ctx, &HK_FLOWER_FANS));
+    }
+    fans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fan_ids(res: &HkResult) -> Vec<(usize, usize)> {
+        res.fans.iter().map(|(f, n)| (f.id, *n)).collect()
+    }
+
+    #[test]
+    fn score_hk_works_01() {
+        // 對對糊, 混一色, 番子, 無花
+        let ctx = test_ctx("111m777m22z55z+5z (pon 333m)", false, WSO);
+        let res = score_hk(&ctx, &HkRules::default()).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![
+                (HK_NO_FLOWERS, 1),
+                (HK_DRAGON_PUNG, 1),
+                (HK_ALL_PUNGS, 1),
+                (HK_HALF_FLUSH, 1)
+            ]
+        );
+        assert_eq!((res.faan, res.limit, res.unit), (8, false, 256));
+    }
+
+    #[test]
+    fn score_hk_works_02() {
+        // 門前清, 正花 (南家の夏と蘭)
+        let ctx = test_ctx("123m456p789s234s5z+5z 26h", false, WSO);
+        let res = score_hk(&ctx, &HkRules::default()).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![(HK_CONCEALED_HAND, 1), (HK_SEAT_FLOWER, 2)]
+        );
+        assert_eq!(res.faan, 3);
+
+        // 春夏秋冬は一台花のみ (夏の正花は数えない), 蘭は正花
+        let ctx = test_ctx("123m456p789s234s5z+5z 12346h", false, WSO);
+        let res = score_hk(&ctx, &HkRules::default()).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![
+                (HK_CONCEALED_HAND, 1),
+                (HK_SEAT_FLOWER, 1),
+                (HK_FLOWER_SET, 1)
+            ]
+        );
+        assert_eq!(res.faan, 4);
+
+        // 花牌がない場合は無花のみで3番に届かない
+        let ctx = test_ctx("123m456p789s234s5z+5z", false, WSO);
+        assert_eq!(
+            score_hk(&ctx, &HkRules::default()).unwrap_err(),
+            MahjongError::InsufficientFan(3, 2)
+        );
+    }
+
+    #[test]
+    fn score_hk_works_03() {
+        // 十三么は上限の番数
+        let ctx = test_ctx("19m19p19s1234567z+1z", true, WSO);
+        let rules = HkRules {
+            max_faan: 10,
+            ..HkRules::default()
+        };
+        let res = score_hk(&ctx, &rules).unwrap();
+        assert_eq!(fan_ids(&res), vec![(HK_THIRTEEN_ORPHANS, 1)]);
+        assert_eq!((res.faan, res.limit, res.unit), (10, true, 1024));
+    }
+
+    #[test]
+    fn score_hk_works_04() {
+        // 海底撈月は自摸のみ (河底のロンは数えない)
+        let rules = HkRules {
+            min_faan: 0,
+            ..HkRules::default()
+        };
+        let mut ctx = test_ctx("123m456p789s234s5z+5z", true, WSO);
+        ctx.yaku_flags.haiteiraoyue = true;
+        let res = score_hk(&ctx, &rules).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![
+                (HK_SELF_DRAWN, 1),
+                (HK_CONCEALED_HAND, 1),
+                (HK_NO_FLOWERS, 1),
+                (HK_LAST_TILE_DRAW, 1)
+            ]
+        );
+
+        let mut ctx = test_ctx("123m456p789s234s5z+5z", false, WSO);
+        ctx.yaku_flags.houteiraoyui = true;
+        let res = score_hk(&ctx, &rules).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![(HK_CONCEALED_HAND, 1), (HK_NO_FLOWERS, 1)]
+        );
+    }
+
+    #[test]
+    fn get_payments_hk_works_01() {
+        let rules = HkRules::default();
+        assert_eq!(
+            get_payments_hk(3, &rules, 0, Some(2)),
+            Ok(vec![32, -8, -16, -8])
+        );
+        assert_eq!(
+            get_payments_hk(3, &rules, 1, None),
+            Ok(vec![-16, 48, -16, -16])
+        );
+        let rules = HkRules {
+            full_shooter: true,
+            ..rules
+        };
+        assert_eq!(
+            get_payments_hk(3, &rules, 0, Some(2)),
+            Ok(vec![32, 0, -32, 0])
+        );
+
+        // 座席番号が不正
+        assert_eq!(
+            get_payments_hk(3, &rules, 4, None),
+            Err(MahjongError::InvalidSeat(4))
+        );
+        assert_eq!(
+            get_payments_hk(3, &rules, 2, Some(2)),
+            Err(MahjongError::InvalidSeat(2))
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use super::*;
+
+#[derive(Debug, Clone)]
+pub struct Wait {
+    pub tile: Tile,                // 待ち牌
+    pub wait_types: Vec<WaitType>, // この牌でアガった場合にとりうる待ちの形
+    pub yaku_nashi: bool,          // どの解釈でも役がない (ドラは役に含まない)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WaitInfo {
+    pub waits: Vec<Wait>, // 待ち牌の一覧
+}
+
+impl WaitInfo {
+    pub fn is_tenpai(&self) -> bool {
+        !self.waits.is_empty()
+    }
+
+    // 3種類以上の多面張
+    pub fn multi_sided(&self) -> bool {
+        self.waits.len() >= 3
+    }
+}
+
+// 3 * mentsu + 1 枚 (鳴きを含む) の手牌の待ちを列挙する. ctx.agari_tile は無視する
+// 手牌と鳴きで4枚使っている牌 (純カラ) とルール上使用しない牌は待ちに含めない
+pub fn find_waits(ctx: &HandContext, rules: &Ruleset) -> WaitInfo {
+    if ctx.hand_tiles.len() + 3 * ctx.fuuro.len() != 3 * ctx.mentsu + 1 {
+        return WaitInfo::default();
+    }
+
+    let mut used = TileTable::default();
+    let fuuro_tiles = ctx.fuuro.iter().flat_map(|Fuuro(_, v)| v);
+    for TileWithDora(t, _) in ctx.hand_tiles.iter().chain(fuuro_tiles) {
+        used[t.0][t.1] += 1;
+    }
+
+    let mut waits = vec![];
+    let mut ctx = ctx.clone();
+    for (i, j) in tile_kinds() {
+        if used[i][j] >= TILE || !rules.is_available(&Tile(i, j)) {
+            continue;
+        }
+        ctx.agari_tile = TileWithDora(Tile(i, j), 0);
+        let yaku_ctxs = detect_winning(&ctx);
+        if yaku_ctxs.is_empty() {
+            continue;
+        }
+
+        let mut wait_types = vec![];
+        let mut yaku_nashi = true;
+        for yaku_ctx in &yaku_ctxs {
+            wait_types.extend(yaku_ctx.wait_types());
+            if !yaku_ctx.calc_yaku(rules).yaku.is_empty() {
+                yaku_nashi = false;
+            }
+        }
+        wait_types.sort();
+        wait_types.dedup();
+
+        waits.push(Wait {
+            tile: Tile(i, j),
+            wait_types,
+            yaku_nashi,
+        });
+    }
+
+    WaitInfo { waits }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_waits_works_01() {
+        // 両面待ち, 役牌あり
+        let info = find_waits(
+            &test_ctx("23m456p789s11z555z+1m", false, WSO),
+            &Ruleset::default(),
+        );
+        let tiles: Vec<Tile> = info.waits.iter().map(|w| w.tile).collect();
+        assert_eq!(tiles, vec![Tile(TM, 1), Tile(TM, 4)]);
+        assert_eq!(info.waits[0].wait_types, vec![WaitType::Ryanmen]);
+        assert!(!info.waits[0].yaku_nashi);
+        assert!(!info.multi_sided());
+    }
+
+    #[test]
+    fn find_waits_works_02() {
+        // シャンポン待ち, 役なし
+        let info = find_waits(
+            &test_ctx("123m456p789s3344z+3z", false, WSO),
+            &Ruleset::default(),
+        );
+        assert_eq!(info.waits.len(), 2);
+        for w in &info.waits {
+            assert_eq!(w.wait_types, vec![WaitType::Shanpon]);
+            assert!(w.yaku_nashi);
+        }
+    }
+
+    #[test]
+    fn find_waits_works_03() {
+        // 延べ単待ち
+        let info = find_waits(
+            &test_ctx("2345m+2m (pon 777z) (pon 111p) (chi 789s)", false, WSO),
+            &Ruleset::default(),
+        );
+        let tiles: Vec<Tile> = info.waits.iter().map(|w| w.tile).collect();
+        assert_eq!(tiles, vec![Tile(TM, 2), Tile(TM, 5)]);
+        assert!(info.waits[0].wait_types.contains(&WaitType::Nobetan));
+    }
+
+    #[test]
+    fn find_waits_works_04() {
+        // 多面張
+        let info = find_waits(
+            &test_ctx("2345678m+2m (pon 777z) (pon 111p)", false, WSO),
+            &Ruleset::default(),
+        );
+        let tiles: Vec<Tile> = info.waits.iter().map(|w| w.tile).collect();
+        assert_eq!(tiles, vec![Tile(TM, 2), Tile(TM, 5), Tile(TM, 8)]);
+        assert!(info.multi_sided());
+    }
+
+    #[test]
+    fn find_waits_works_05() {
+        // 4枚使いの牌は待ちにしない
+        let info = find_waits(
+            &test_ctx("1111m456p789s555z+2m", false, WSO),
+            &Ruleset::default(),
+        );
+        assert!(!info.is_tenpai());
+    }
+}
@@ -0,0 +1,1254 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+// 中国麻将 (国標麻将) の番種ID
+const BIG_FOUR_WINDS: usize = 1;
+const BIG_THREE_DRAGONS: usize = 2;
+const ALL_GREEN: usize = 3;
+const NINE_GATES: usize = 4;
+const FOUR_KONGS: usize = 5;
+const SEVEN_SHIFTED_PAIRS: usize = 6;
+const THIRTEEN_ORPHANS: usize = 7;
+const ALL_TERMINALS: usize = 8;
+const LITTLE_FOUR_WINDS: usize = 9;
+const LITTLE_THREE_DRAGONS: usize = 10;
+const ALL_HONORS: usize = 11;
+const FOUR_CONCEALED_PUNGS: usize = 12;
+const PURE_TERMINAL_CHOWS: usize = 13;
+const QUADRUPLE_CHOW: usize = 14;
+const FOUR_PURE_SHIFTED_PUNGS: usize = 15;
+const FOUR_PURE_SHIFTED_CHOWS: usize = 16;
+const THREE_KONGS: usize = 17;
+const ALL_TERMINALS_AND_HONORS: usize = 18;
+const SEVEN_PAIRS: usize = 19;
+const GREATER_HONORS_AND_KNITTED: usize = 20;
+const ALL_EVEN_PUNGS: usize = 21;
+const FULL_FLUSH: usize = 22;
+const PURE_TRIPLE_CHOW: usize = 23;
+const PURE_SHIFTED_PUNGS: usize = 24;
+const UPPER_TILES: usize = 25;
+const MIDDLE_TILES: usize = 26;
+const LOWER_TILES: usize = 27;
+const PURE_STRAIGHT: usize = 28;
+const THREE_SUITED_TERMINAL_CHOWS: usize = 29;
+const PURE_SHIFTED_CHOWS: usize = 30;
+const ALL_FIVES: usize = 31;
+const TRIPLE_PUNG: usize = 32;
+const THREE_CONCEALED_PUNGS: usize = 33;
+const LESSER_HONORS_AND_KNITTED: usize = 34;
+const KNITTED_STRAIGHT: usize = 35;
+const UPPER_FOUR: usize = 36;
+const LOWER_FOUR: usize = 37;
+const BIG_THREE_WINDS: usize = 38;
+const MIXED_STRAIGHT: usize = 39;
+const REVERSIBLE_TILES: usize = 40;
+const MIXED_TRIPLE_CHOW: usize = 41;
+const MIXED_SHIFTED_PUNGS: usize = 42;
+const CHICKEN_HAND: usize = 43;
+const LAST_TILE_DRAW: usize = 44;
+const LAST_TILE_CLAIM: usize = 45;
+const OUT_WITH_REPLACEMENT_TILE: usize = 46;
+const ROBBING_THE_KONG: usize = 47;
+const ALL_PUNGS: usize = 48;
+const HALF_FLUSH: usize = 49;
+const MIXED_SHIFTED_CHOWS: usize = 50;
+const ALL_TYPES: usize = 51;
+const MELDED_HAND: usize = 52;
+const TWO_CONCEALED_KONGS: usize = 53;
+const TWO_DRAGON_PUNGS: usize = 54;
+const OUTSIDE_HAND: usize = 55;
+const FULLY_CONCEALED_HAND: usize = 56;
+const TWO_MELDED_KONGS: usize = 57;
+const LAST_TILE: usize = 58;
+const DRAGON_PUNG: usize = 59;
+const PREVALENT_WIND: usize = 60;
+const SEAT_WIND: usize = 61;
+const CONCEALED_HAND: usize = 62;
+const ALL_CHOWS: usize = 63;
+const TILE_HOG: usize = 64;
+const DOUBLE_PUNG: usize = 65;
+const TWO_CONCEALED_PUNGS: usize = 66;
+const CONCEALED_KONG: usize = 67;
+const ALL_SIMPLES: usize = 68;
+const PURE_DOUBLE_CHOW: usize = 69;
+const MIXED_DOUBLE_CHOW: usize = 70;
+const SHORT_STRAIGHT: usize = 71;
+const TWO_TERMINAL_CHOWS: usize = 72;
+const PUNG_OF_TERMINALS_OR_HONORS: usize = 73;
+const MELDED_KONG: usize = 74;
+const ONE_VOIDED_SUIT: usize = 75;
+const NO_HONORS: usize = 76;
+const EDGE_WAIT: usize = 77;
+const CLOSED_WAIT: usize = 78;
+const SINGLE_WAIT: usize = 79;
+const SELF_DRAWN: usize = 80;
+const FLOWER_TILES: usize = 81;
+
+// 中国麻将の番種 (番種ID順)
+pub const MCR_FAN_LIST: &[Fan] = &[
+    // 88番
+    fan!(BIG_FOUR_WINDS, "大四喜", 88),
+    fan!(BIG_THREE_DRAGONS, "大三元", 88),
+    fan!(ALL_GREEN, "绿一色", 88),
+    fan!(NINE_GATES, "九莲宝灯", 88),
+    fan!(FOUR_KONGS, "四杠", 88),
+    fan!(SEVEN_SHIFTED_PAIRS, "连七对", 88),
+    fan!(THIRTEEN_ORPHANS, "十三幺", 88),
+    // 64番
+    fan!(ALL_TERMINALS, "清幺九", 64),
+    fan!(LITTLE_FOUR_WINDS, "小四喜", 64),
+    fan!(LITTLE_THREE_DRAGONS, "小三元", 64),
+    fan!(ALL_HONORS, "字一色", 64),
+    fan!(FOUR_CONCEALED_PUNGS, "四暗刻", 64),
+    fan!(PURE_TERMINAL_CHOWS, "一色双龙会", 64),
+    // 48番
+    fan!(QUADRUPLE_CHOW, "一色四同顺", 48),
+    fan!(FOUR_PURE_SHIFTED_PUNGS, "一色四节高", 48),
+    // 32番
+    fan!(FOUR_PURE_SHIFTED_CHOWS, "一色四步高", 32),
+    fan!(THREE_KONGS, "三杠", 32),
+    fan!(ALL_TERMINALS_AND_HONORS, "混幺九", 32),
+    // 24番
+    fan!(SEVEN_PAIRS, "七对", 24),
+    fan!(GREATER_HONORS_AND_KNITTED, "七星不靠", 24),
+    fan!(ALL_EVEN_PUNGS, "全双刻", 24),
+    fan!(FULL_FLUSH, "清一色", 24),
+    fan!(PURE_TRIPLE_CHOW, "一色三同顺", 24),
+    fan!(PURE_SHIFTED_PUNGS, "一色三节高", 24),
+    fan!(UPPER_TILES, "全大", 24),
+    fan!(MIDDLE_TILES, "全中", 24),
+    fan!(LOWER_TILES, "全小", 24),
+    // 16番
+    fan!(PURE_STRAIGHT, "清龙", 16),
+    fan!(THREE_SUITED_TERMINAL_CHOWS, "三色双龙会", 16),
+    fan!(PURE_SHIFTED_CHOWS, "一色三步高", 16),
+    fan!(ALL_FIVES, "全带五", 16),
+    fan!(TRIPLE_PUNG, "三同刻", 16),
+    fan!(THREE_CONCEALED_PUNGS, "三暗刻", 16),
+    // 12番
+    fan!(LESSER_HONORS_AND_KNITTED, "全不靠", 12),
+    fan!(KNITTED_STRAIGHT, "组合龙", 12),
+    fan!(UPPER_FOUR, "大于五", 12),
+    fan!(LOWER_FOUR, "小于五", 12),
+    fan!(BIG_THREE_WINDS, "三风刻", 12),
+    // 8番
+    fan!(MIXED_STRAIGHT, "花龙", 8),
+    fan!(REVERSIBLE_TILES, "推不倒", 8),
+    fan!(MIXED_TRIPLE_CHOW, "三色三同顺", 8),
+    fan!(MIXED_SHIFTED_PUNGS, "三色三节高", 8),
+    fan!(CHICKEN_HAND, "无番和", 8),
+    fan!(LAST_TILE_DRAW, "妙手回春", 8),
+    fan!(LAST_TILE_CLAIM, "海底捞月", 8),
+    fan!(OUT_WITH_REPLACEMENT_TILE, "杠上开花", 8),
+    fan!(ROBBING_THE_KONG, "抢杠和", 8),
+    // 6番
+    fan!(ALL_PUNGS, "碰碰和", 6),
+    fan!(HALF_FLUSH, "混一色", 6),
+    fan!(MIXED_SHIFTED_CHOWS, "三色三步高", 6),
+    fan!(ALL_TYPES, "五门齐", 6),
+    fan!(MELDED_HAND, "全求人", 6),
+    fan!(TWO_CONCEALED_KONGS, "双暗杠", 6),
+    fan!(TWO_DRAGON_PUNGS, "双箭刻", 6),
+    // 4番
+    fan!(OUTSIDE_HAND, "全带幺", 4),
+    fan!(FULLY_CONCEALED_HAND, "不求人", 4),
+    fan!(TWO_MELDED_KONGS, "双明杠", 4),
+    fan!(LAST_TILE, "和绝张", 4),
+    // 2番
+    fan!(DRAGON_PUNG, "箭刻", 2),
+    fan!(PREVALENT_WIND, "圈风刻", 2),
+    fan!(SEAT_WIND, "门风刻", 2),
+    fan!(CONCEALED_HAND, "门前清", 2),
+    fan!(ALL_CHOWS, "平和", 2),
+    fan!(TILE_HOG, "四归一", 2),
+    fan!(DOUBLE_PUNG, "双同刻", 2),
+    fan!(TWO_CONCEALED_PUNGS, "双暗刻", 2),
+    fan!(CONCEALED_KONG, "暗杠", 2),
+    fan!(ALL_SIMPLES, "断幺", 2),
+    // 1番
+    fan!(PURE_DOUBLE_CHOW, "一般高", 1),
+    fan!(MIXED_DOUBLE_CHOW, "喜相逢", 1),
+    fan!(SHORT_STRAIGHT, "连六", 1),
+    fan!(TWO_TERMINAL_CHOWS, "老少副", 1),
+    fan!(PUNG_OF_TERMINALS_OR_HONORS, "幺九刻", 1),
+    fan!(MELDED_KONG, "明杠", 1),
+    fan!(ONE_VOIDED_SUIT, "缺一门", 1),
+    fan!(NO_HONORS, "无字", 1),
+    fan!(EDGE_WAIT, "边张", 1),
+    fan!(CLOSED_WAIT, "嵌张", 1),
+    fan!(SINGLE_WAIT, "单钓将", 1),
+    fan!(SELF_DRAWN, "自摸", 1),
+    fan!(FLOWER_TILES, "花牌", 1),
+];
+
+// 不計原則: 番種が成立した場合に計上しない番種
+const MCR_EXCLUSIONS: &[(usize, &[usize])] = &[
+    (
+        BIG_FOUR_WINDS,
+        &[
+            BIG_THREE_WINDS,
+            ALL_PUNGS,
+            PREVALENT_WIND,
+            SEAT_WIND,
+            PUNG_OF_TERMINALS_OR_HONORS,
+        ],
+    ),
+    (BIG_THREE_DRAGONS, &[TWO_DRAGON_PUNGS, DRAGON_PUNG]),
+    (ALL_GREEN, &[HALF_FLUSH]),
+    (
+        NINE_GATES,
+        &[
+            FULL_FLUSH,
+            CONCEALED_HAND,
+            PUNG_OF_TERMINALS_OR_HONORS,
+            NO_HONORS,
+        ],
+    ),
+    (FOUR_KONGS, &[SINGLE_WAIT]),
+    (
+        SEVEN_SHIFTED_PAIRS,
+        &[
+            SEVEN_PAIRS,
+            FULL_FLUSH,
+            CONCEALED_HAND,
+            NO_HONORS,
+            SINGLE_WAIT,
+        ],
+    ),
+    (
+        THIRTEEN_ORPHANS,
+        &[
+            ALL_TERMINALS_AND_HONORS,
+            ALL_TYPES,
+            CONCEALED_HAND,
+            SINGLE_WAIT,
+        ],
+    ),
+    (
+        ALL_TERMINALS,
+        &[
+            ALL_TERMINALS_AND_HONORS,
+            ALL_PUNGS,
+            OUTSIDE_HAND,
+            DOUBLE_PUNG,
+            PUNG_OF_TERMINALS_OR_HONORS,
+            NO_HONORS,
+        ],
+    ),
+    (LITTLE_FOUR_WINDS, &[BIG_THREE_WINDS]),
+    (LITTLE_THREE_DRAGONS, &[TWO_DRAGON_PUNGS, DRAGON_PUNG]),
+    (
+        ALL_HONORS,
+        &[
+            ALL_TERMINALS_AND_HONORS,
+            ALL_PUNGS,
+            OUTSIDE_HAND,
+            PUNG_OF_TERMINALS_OR_HONORS,
+        ],
+    ),
+    (FOUR_CONCEALED_PUNGS, &[ALL_PUNGS, CONCEALED_HAND]),
+    (
+        PURE_TERMINAL_CHOWS,
+        &[
+            FULL_FLUSH,
+            ALL_CHOWS,
+            PURE_DOUBLE_CHOW,
+            TWO_TERMINAL_CHOWS,
+            NO_HONORS,
+        ],
+    ),
+    (
+        QUADRUPLE_CHOW,
+        &[PURE_TRIPLE_CHOW, TILE_HOG, PURE_DOUBLE_CHOW],
+    ),
+    (FOUR_PURE_SHIFTED_PUNGS, &[PURE_SHIFTED_PUNGS, ALL_PUNGS]),
+    (
+        FOUR_PURE_SHIFTED_CHOWS,
+        &[PURE_SHIFTED_CHOWS, SHORT_STRAIGHT, TWO_TERMINAL_CHOWS],
+    ),
+    (
+        ALL_TERMINALS_AND_HONORS,
+        &[ALL_PUNGS, OUTSIDE_HAND, PUNG_OF_TERMINALS_OR_HONORS],
+    ),
+    (SEVEN_PAIRS, &[CONCEALED_HAND, SINGLE_WAIT]),
+    (
+        GREATER_HONORS_AND_KNITTED,
+        &[
+            LESSER_HONORS_AND_KNITTED,
+            ALL_TYPES,
+            CONCEALED_HAND,
+            SINGLE_WAIT,
+        ],
+    ),
+    (ALL_EVEN_PUNGS, &[ALL_PUNGS, ALL_SIMPLES]),
+    (FULL_FLUSH, &[ONE_VOIDED_SUIT, NO_HONORS]),
+    (PURE_TRIPLE_CHOW, &[PURE_SHIFTED_PUNGS, PURE_DOUBLE_CHOW]),
+    (PURE_SHIFTED_PUNGS, &[PURE_TRIPLE_CHOW]),
+    (UPPER_TILES, &[UPPER_FOUR, NO_HONORS]),
+    (MIDDLE_TILES, &[ALL_SIMPLES, NO_HONORS]),
+    (LOWER_TILES, &[LOWER_FOUR, NO_HONORS]),
+    (PURE_STRAIGHT, &[SHORT_STRAIGHT, TWO_TERMINAL_CHOWS]),
+    (
+        THREE_SUITED_TERMINAL_CHOWS,
+        &[ALL_CHOWS, MIXED_DOUBLE_CHOW, TWO_TERMINAL_CHOWS, NO_HONORS],
+    ),
+    (ALL_FIVES, &[ALL_SIMPLES]),
+    (TRIPLE_PUNG, &[DOUBLE_PUNG]),
+    (THREE_CONCEALED_PUNGS, &[TWO_CONCEALED_PUNGS]),
+    (
+        LESSER_HONORS_AND_KNITTED,
+        &[ALL_TYPES, CONCEALED_HAND, SINGLE_WAIT],
+    ),
+    (UPPER_FOUR, &[NO_HONORS]),
+    (LOWER_FOUR, &[NO_HONORS]),
+    (REVERSIBLE_TILES, &[ONE_VOIDED_SUIT]),
+    (LAST_TILE_DRAW, &[SELF_DRAWN]),
+    (OUT_WITH_REPLACEMENT_TILE, &[SELF_DRAWN]),
+    (ROBBING_THE_KONG, &[LAST_TILE]),
+    (MELDED_HAND, &[SINGLE_WAIT]),
+    (TWO_CONCEALED_KONGS, &[TWO_CONCEALED_PUNGS, CONCEALED_KONG]),
+    (TWO_DRAGON_PUNGS, &[DRAGON_PUNG]),
+    (FULLY_CONCEALED_HAND, &[CONCEALED_HAND, SELF_DRAWN]),
+    (TWO_MELDED_KONGS, &[MELDED_KONG]),
+    (ALL_CHOWS, &[NO_HONORS]),
+];
+
+// 和了の状況による番種
+const MCR_SITUATION_FANS: SituationFans = SituationFans {
+    haitei: Some(LAST_TILE_DRAW),
+    houtei: Some(LAST_TILE_CLAIM),
+    rinshan: Some(OUT_WITH_REPLACEMENT_TILE),
+    chankan: Some(ROBBING_THE_KONG),
+    tenhou: None,
+    tiihou: None,
+    last_tile: Some(LAST_TILE),
+};
+
+// 1番あたりの点数 (基本点)
+pub const MCR_BASE_POINT: Point = 8;
+
+// 中国麻将のルール設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct McrRules {
+    pub min_fan: usize, // 和了に必要な番数 (花牌を含まない)
+    pub flowers: bool,  // 花牌を1枚1番として数える
+}
+
+impl Default for McrRules {
+    fn default() -> Self {
+        Self {
+            min_fan: 8,
+            flowers: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct McrResult {
+    pub fans: Vec<(&'static Fan, usize)>, // 番種と成立数 (花牌を含む)
+    pub fan: usize,                       // 番数 (花牌を含まない)
+    pub flower_fan: usize,                // 花牌の番数
+    pub total: usize,                     // 番数の合計
+}
+
+// 和了形の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum McrForm {
+    Standard,         // 4面子1雀頭
+    SevenPairs,       // 七対
+    ThirteenOrphans,  // 十三幺
+    HonorsAndKnitted, // 全不靠
+    KnittedStraight,  // 组合龙 + 1面子1雀頭
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SetKind {
+    Chow, // 順子
+    Pung, // 刻子
+    Kong, // 槓子
+}
+
+// Tileは順子の場合は先頭の牌
+#[derive(Debug, Clone, Copy)]
+struct McrSet {
+    kind: SetKind,
+    tile: Tile,
+    concealed: bool, // 暗刻, 暗槓 (ロンで完成した刻子は明刻)
+}
+
+impl McrSet {
+    fn tiles(&self) -> [Tile; 3] {
+        let Tile(t, n) = self.tile;
+        match self.kind {
+            SetKind::Chow => [Tile(t, n), Tile(t, n + 1), Tile(t, n + 2)],
+            _ => [self.tile; 3],
+        }
+    }
+}
+
+struct McrHand {
+    form: McrForm,
+    sets: Vec<McrSet>,
+    pair: Option<Tile>,
+    tiles: TileTable,       // 和了形の牌 (槓子は3枚として数える)
+    wait: Option<WaitType>, // 待ち牌が1種類の場合の待ちの形
+}
+
+// 和了形の解釈のうち最も番数の多いものを返却
+pub fn score_mcr(ctx: &HandContext, rules: &McrRules) -> Result<McrResult, MahjongError> {
+    ctx.check_mentsu(4)?;
+    ctx.check_tiles()?;
+
+    let single_wait = wait_count(ctx, |c| !mcr_hands(c, false).is_empty()) == 1;
+    let Some(mut fans) = mcr_hands(ctx, single_wait)
+        .iter()
+        .map(|h| count_fans(ctx, h))
+        .max_by_key(|fans| fan_sum(MCR_FAN_LIST, fans))
+    else {
+        return Err(MahjongError::NotWinning);
+    };
+
+    let fan = fan_sum(MCR_FAN_LIST, &fans);
+    if fan < rules.min_fan {
+        return Err(MahjongError::InsufficientFan(rules.min_fan, fan));
+    }
+
+    let flower_fan = if rules.flowers { ctx.flowers.len() } else { 0 };
+    fans.extend(std::iter::repeat_n(FLOWER_TILES, flower_fan));
+
+    Ok(McrResult {
+        fans: merge_fans(MCR_FAN_LIST, &fans),
+        fan,
+        flower_fan,
+        total: fan + flower_fan,
+    })
+}
+
+// 各座席の点数の増減を返却 (loser が None の場合は自摸和了)
+// 放銃者は 8 + 番数, それ以外は 8 を支払う. 自摸和了は全員が 8 + 番数 を支払う
+pub fn get_payments_mcr(
+    fan: usize,
+    winner: Seat,
+    loser: Option<Seat>,
+) -> Result<Vec<Point>, MahjongError> {
+    check_seats(SEAT, winner, loser)?;
+
+    let mut deltas = vec![0; SEAT];
+    let fan = fan as Point;
+    for seat in 0..SEAT {
+        if seat == winner {
+            continue;
+        }
+        let p = match loser {
+            Some(loser) if loser != seat => MCR_BASE_POINT,
+            _ => MCR_BASE_POINT + fan,
+        };
+        deltas[seat] -= p;
+        deltas[winner] += p;
+    }
+    Ok(deltas)
+}
+
+// 手牌とアガり牌 (鳴きは含まない)
+fn concealed_table(ctx: &HandContext) -> TileTable {
+    let mut table = TileTable::default();
+    for TileWithDora(t, _) in ctx
+        .hand_tiles
+        .iter()
+        .chain(std::iter::once(&ctx.agari_tile))
+    {
+        table[t.0][t.1] += 1;
+    }
+    table
+}
+
+fn fuuro_set(Fuuro(ft, v): &Fuuro) -> McrSet {
+    let tile = v
+        .iter()
+        .map(|TileWithDora(t, _)| *t)
+        .min_by_key(|t| t.1)
+        .unwrap();
+    let (kind, concealed) = match ft {
+        FuuroType::Chi => (SetKind::Chow, false),
+        FuuroType::Pon => (SetKind::Pung, false),
+        FuuroType::Minkan => (SetKind::Kong, false),
+        FuuroType::Ankan => (SetKind::Kong, true),
+    };
+    McrSet {
+        kind,
+        tile,
+        concealed,
+    }
+}
+
+fn sets_table(sets: &[McrSet], pair: Option<Tile>) -> TileTable {
+    let mut table = TileTable::default();
+    for s in sets {
+        for t in s.tiles() {
+            table[t.0][t.1] += 1;
+        }
+    }
+    if let Some(p) = pair {
+        table[p.0][p.1] += 2;
+    }
+    table
+}
+
+// 组合龙の147, 258, 369の種別の組み合わせ
+const KNITTED: [[Type; 3]; 6] = [
+    [TM, TP, TS],
+    [TM, TS, TP],
+    [TP, TM, TS],
+    [TP, TS, TM],
+    [TS, TM, TP],
+    [TS, TP, TM],
+];
+
+fn knitted_tiles(perm: &[Type; 3]) -> impl Iterator<Item = Tile> + '_ {
+    perm.iter()
+        .enumerate()
+        .flat_map(|(k, &t)| [1, 4, 7].into_iter().map(move |n| Tile(t, n + k)))
+}
+
+// 和了形の解釈を列挙する (single_wait は待ち牌が1種類)
+fn mcr_hands(ctx: &HandContext, single_wait: bool) -> Vec<McrHand> {
+    let mut hands = vec![];
+
+    // 4面子1雀頭
+    for yaku_ctx in detect_winning(ctx) {
+        let YakuForm::FiveBlock(fb) = yaku_ctx.form() else {
+            continue;
+        };
+        let agari = fb.agari_block();
+        let mut sets = vec![];
+        let mut pair = None;
+        for (i, &Block(bt, t)) in fb.blocks().iter().enumerate() {
+            let (kind, concealed) = match bt {
+                BlockType::Pair => {
+                    pair = Some(t);
+                    continue;
+                }
+                BlockType::Shuntsu => (SetKind::Chow, true),
+                BlockType::Koutsu => (SetKind::Pung, i != agari.index || agari.closed),
+                BlockType::Chi => (SetKind::Chow, false),
+                BlockType::Pon => (SetKind::Pung, false),
+                BlockType::Minkan => (SetKind::Kong, false),
+                BlockType::Ankan => (SetKind::Kong, true),
+            };
+            sets.push(McrSet {
+                kind,
+                tile: t,
+                concealed,
+            });
+        }
+        hands.push(McrHand {
+            form: McrForm::Standard,
+            tiles: sets_table(&sets, pair),
+            sets,
+            pair,
+            wait: single_wait.then(|| yaku_ctx.wait_type()),
+        });
+    }
+
+    let table = concealed_table(ctx);
+    let count: usize = table.iter().flatten().sum();
+    let kinds: Vec<Tile> = tile_kinds()
+        .map(|(i, j)| Tile(i, j))
+        .filter(|t| table[t.0][t.1] > 0)
+        .collect();
+    let other = |form| McrHand {
+        form,
+        sets: vec![],
+        pair: None,
+        tiles: table,
+        wait: None,
+    };
+
+    if ctx.fuuro.is_empty() && count == 14 {
+        // 七対 (4枚使いは2組の対子とする)
+        if kinds.iter().all(|t| table[t.0][t.1].is_multiple_of(2)) {
+            hands.push(other(McrForm::SevenPairs));
+        }
+        // 十三幺
+        if kinds.len() == 13 && kinds.iter().all(|t| t.is_end()) {
+            hands.push(other(McrForm::ThirteenOrphans));
+        }
+        // 全不靠
+        if kinds.len() == 14 {
+            let knitted = KNITTED.iter().any(|perm| {
+                kinds
+                    .iter()
+                    .filter(|t| t.is_suited())
+                    .all(|t| knitted_tiles(perm).any(|k| k == *t))
+            });
+            if knitted {
+                hands.push(other(McrForm::HonorsAndKnitted));
+            }
+        }
+    }
+
+    // 组合龙 + 1面子1雀頭
+    if ctx.fuuro.len() <= 1 {
+        for perm in &KNITTED {
+            let mut rest = table;
+            if !knitted_tiles(perm).all(|t| rest[t.0][t.1] > 0) {
+                continue;
+            }
+            for t in knitted_tiles(perm) {
+                rest[t.0][t.1] -= 1;
+            }
+            let fuuro_sets: Vec<McrSet> = ctx.fuuro.iter().map(fuuro_set).collect();
+            for (i, j) in tile_kinds() {
+                if rest[i][j] < 2 {
+                    continue;
+                }
+                let mut rest = rest;
+                rest[i][j] -= 2;
+                let mut sets = fuuro_sets.clone();
+                if let Some(set) = rest_set(&rest) {
+                    sets.push(set);
+                } else if sets.is_empty() {
+                    continue;
+                }
+                let pair = Some(Tile(i, j));
+                let mut tiles = sets_table(&sets, pair);
+                for t in knitted_tiles(perm) {
+                    tiles[t.0][t.1] += 1;
+                }
+                hands.push(McrHand {
+                    form: McrForm::KnittedStraight,
+                    sets,
+                    pair,
+                    tiles,
+                    wait: None,
+                });
+            }
+        }
+    }
+
+    hands
+}
+
+// 残り3枚が面子になる場合はその面子を返却
+fn rest_set(rest: &TileTable) -> Option<McrSet> {
+    let tiles: Vec<Tile> = tile_kinds()
+        .map(|(i, j)| Tile(i, j))
+        .flat_map(|t| std::iter::repeat_n(t, rest[t.0][t.1]))
+        .collect();
+    if tiles.len() != 3 {
+        return None;
+    }
+    let kind = if tiles[0] == tiles[1] && tiles[1] == tiles[2] {
+        SetKind::Pung
+    } else if tiles[0].is_suited()
+        && tiles.iter().all(|t| t.0 == tiles[0].0)
+        && tiles[1].1 == tiles[0].1 + 1
+        && tiles[2].1 == tiles[0].1 + 2
+    {
+        SetKind::Chow
+    } else {
+        return None;
+    };
+    Some(McrSet {
+        kind,
+        tile: tiles[0],
+        concealed: true,
+    })
+}
+
+// 成立する番種IDを列挙する (不計原則を適用済み, 花牌は含まない)
+fn count_fans(ctx: &HandContext, hand: &McrHand) -> Vec<usize> {
+    let mut fans = vec![];
+    let kinds: Vec<Tile> = tile_kinds()
+        .map(|(i, j)| Tile(i, j))
+        .filter(|t| hand.tiles[t.0][t.1] > 0)
+        .collect();
+    let all = |f: fn(&Tile) -> bool| kinds.iter().all(f);
+    let chows: Vec<Tile> = hand
+        .sets
+        .iter()
+        .filter(|s| s.kind == SetKind::Chow)
+        .map(|s| s.tile)
+        .collect();
+    let pungs: Vec<Tile> = hand
+        .sets
+        .iter()
+        .filter(|s| s.kind != SetKind::Chow)
+        .map(|s| s.tile)
+        .collect();
+
+    // 和了形
+    match hand.form {
+        McrForm::ThirteenOrphans => fans.push(THIRTEEN_ORPHANS),
+        McrForm::SevenPairs => {
+            let shifted = kinds.len() == 7
+                && kinds.iter().all(|t| t.is_suited() && t.0 == kinds[0].0)
+                && kinds[6].1 == kinds[0].1 + 6;
+            fans.push(if shifted {
+                SEVEN_SHIFTED_PAIRS
+            } else {
+                SEVEN_PAIRS
+            });
+        }
+        McrForm::HonorsAndKnitted => {
+            let honors = kinds.iter().filter(|t| t.is_honor()).count();
+            fans.push(if honors == 7 {
+                GREATER_HONORS_AND_KNITTED
+            } else {
+                LESSER_HONORS_AND_KNITTED
+            });
+            if kinds.len() - honors == 9 {
+                fans.push(KNITTED_STRAIGHT);
+            }
+        }
+        McrForm::KnittedStraight => fans.push(KNITTED_STRAIGHT),
+        McrForm::Standard => {
+            if chows.len() == 4 && hand.pair.is_some_and(|p| p.is_suited()) {
+                fans.push(ALL_CHOWS);
+            }
+            let sets_and_pair = || {
+                hand.sets
+                    .iter()
+                    .map(|s| s.tiles().to_vec())
+                    .chain(hand.pair.map(|p| vec![p]))
+            };
+            if sets_and_pair().all(|v| v.iter().any(|t| t.is_suited() && t.1 == 5)) {
+                fans.push(ALL_FIVES);
+            }
+            if sets_and_pair().all(|v| v.iter().any(|t| t.is_end())) {
+                fans.push(OUTSIDE_HAND);
+            }
+            if is_nine_gates(ctx) {
+                fans.push(NINE_GATES);
+            }
+        }
+    }
+
+    // 順子・刻子の組み合わせ
+    fans.extend(chow_fans(&chows, hand.pair));
+    fans.extend(pung_fans(&pungs));
+    fans.extend(honor_pung_fans(ctx, &pungs, hand.pair));
+    if pungs.len() == 4 {
+        fans.push(ALL_PUNGS);
+    }
+    match hand
+        .sets
+        .iter()
+        .filter(|s| s.kind != SetKind::Chow && s.concealed)
+        .count()
+    {
+        4 => fans.push(FOUR_CONCEALED_PUNGS),
+        3 => fans.push(THREE_CONCEALED_PUNGS),
+        2 => fans.push(TWO_CONCEALED_PUNGS),
+        _ => {}
+    }
+    let kongs = hand.sets.iter().filter(|s| s.kind == SetKind::Kong);
+    let concealed_kongs = kongs.clone().filter(|s| s.concealed).count();
+    match (kongs.count(), concealed_kongs) {
+        (4, _) => fans.push(FOUR_KONGS),
+        (3, _) => fans.push(THREE_KONGS),
+        (2, 2) => fans.push(TWO_CONCEALED_KONGS),
+        (2, 0) => fans.push(TWO_MELDED_KONGS),
+        (2, _) => fans.extend([CONCEALED_KONG, MELDED_KONG]),
+        (1, 1) => fans.push(CONCEALED_KONG),
+        (1, _) => fans.push(MELDED_KONG),
+        _ => {}
+    }
+
+    // 牌の種類
+    let suits = (TM..=TS)
+        .filter(|&i| kinds.iter().any(|t| t.0 == i))
+        .count();
+    let has_honor = kinds.iter().any(|t| t.is_honor());
+    if all(Tile::is_green) {
+        fans.push(ALL_GREEN);
+    }
+    if all(Tile::is_honor) {
+        fans.push(ALL_HONORS);
+    } else if all(Tile::is_terminal) {
+        fans.push(ALL_TERMINALS);
+    } else if all(Tile::is_end) {
+        fans.push(ALL_TERMINALS_AND_HONORS);
+    }
+    if suits == 1 {
+        fans.push(if has_honor { HALF_FLUSH } else { FULL_FLUSH });
+    }
+    if pungs.len() == 4 && all(|t| t.is_suited() && t.1 % 2 == 0) {
+        fans.push(ALL_EVEN_PUNGS);
+    }
+    if all(|t| t.is_suited() && t.1 >= 7) {
+        fans.push(UPPER_TILES);
+    } else if all(|t| t.is_suited() && (4..=6).contains(&t.1)) {
+        fans.push(MIDDLE_TILES);
+    } else if all(|t| t.is_suited() && t.1 <= 3) {
+        fans.push(LOWER_TILES);
+    } else if all(|t| t.is_suited() && t.1 >= 6) {
+        fans.push(UPPER_FOUR);
+    } else if all(|t| t.is_suited() && t.1 <= 4) {
+        fans.push(LOWER_FOUR);
+    }
+    if all(is_reversible) {
+        fans.push(REVERSIBLE_TILES);
+    }
+    if suits == 3 && kinds.iter().any(|t| t.is_wind()) && kinds.iter().any(|t| t.is_dragon()) {
+        fans.push(ALL_TYPES);
+    }
+    if suits == 2 {
+        fans.push(ONE_VOIDED_SUIT);
+    }
+    if !has_honor {
+        fans.push(NO_HONORS);
+    }
+    if all(Tile::is_simple) {
+        fans.push(ALL_SIMPLES);
+    }
+    for t in &kinds {
+        if hand.tiles[t.0][t.1] == TILE {
+            fans.push(TILE_HOG);
+        }
+    }
+
+    // 和了の状況
+    let menzen = ctx
+        .fuuro
+        .iter()
+        .all(|Fuuro(ft, _)| matches!(ft, FuuroType::Ankan));
+    if menzen {
+        fans.push(if ctx.tsumo {
+            FULLY_CONCEALED_HAND
+        } else {
+            CONCEALED_HAND
+        });
+    }
+    if ctx.fuuro.len() == 4 && !menzen && !ctx.tsumo {
+        let all_open = ctx
+            .fuuro
+            .iter()
+            .all(|Fuuro(ft, _)| !matches!(ft, FuuroType::Ankan));
+        if all_open {
+            fans.push(MELDED_HAND);
+        }
+    }
+    fans.extend(situation_fans(ctx, &MCR_SITUATION_FANS));
+    if ctx.tsumo {
+        fans.push(SELF_DRAWN);
+    }
+    match hand.wait {
+        Some(WaitType::Penchan) => fans.push(EDGE_WAIT),
+        Some(WaitType::Kanchan) => fans.push(CLOSED_WAIT),
+        Some(WaitType::Tanki) => fans.push(SINGLE_WAIT),
+        _ => {}
+    }
+
+    // 不計原則
+    let mut excluded = vec![];
+    for (id, ex) in MCR_EXCLUSIONS {
+        if fans.contains(id) {
+            excluded.extend_from_slice(ex);
+        }
+    }
+    fans.retain(|id| !excluded.contains(id));
+    if fans.is_empty() {
+        fans.push(CHICKEN_HAND);
+    }
+    fans
+}
+
+// 推不倒 (上下対称の牌)
+fn is_reversible(t: &Tile) -> bool {
+    match t.0 {
+        TP => matches!(t.1, 1 | 2 | 3 | 4 | 5 | 8 | 9),
+        TS => matches!(t.1, 2 | 4 | 5 | 6 | 8 | 9),
+        TZ => t.1 == DWH,
+        _ => false,
+    }
+}
+
+fn is_nine_gates(ctx: &HandContext) -> bool {
+    if !ctx.fuuro.is_empty() {
+        return false;
+    }
+    let Some(TileWithDora(Tile(i, _), _)) = ctx.hand_tiles.first() else {
+        return false;
+    };
+    let mut counts = [0; 10];
+    for TileWithDora(t, _) in &ctx.hand_tiles {
+        if t.0 != *i || !t.is_suited() {
+            return false;
+        }
+        counts[t.1] += 1;
+    }
+    counts == [0, 3, 1, 1, 1, 1, 1, 1, 1, 3] && ctx.agari_tile.0 .0 == *i
+}
+
+fn sorted(tiles: &[Tile]) -> Vec<Tile> {
+    let mut v = tiles.to_vec();
+    v.sort_by_key(|t| (t.0, t.1));
+    v
+}
+
+fn mcr_fan_value(id: usize) -> usize {
+    fan_entry(MCR_FAN_LIST, id).value
+}
+
+// 2つの順子の組み合わせ (一般高, 喜相逢, 连六, 老少副)
+fn chow_pair_fan(a: Tile, b: Tile) -> Option<usize> {
+    if a == b {
+        Some(PURE_DOUBLE_CHOW)
+    } else if a.1 == b.1 {
+        Some(MIXED_DOUBLE_CHOW)
+    } else if a.0 == b.0 && a.1.abs_diff(b.1) == 3 {
+        Some(SHORT_STRAIGHT)
+    } else if a.0 == b.0 && a.1.min(b.1) == 1 && a.1.max(b.1) == 7 {
+        Some(TWO_TERMINAL_CHOWS)
+    } else {
+        None
+    }
+}
+
+// 3つの順子の組み合わせ
+fn chow_triple_fan(chows: &[Tile]) -> Option<usize> {
+    let c = sorted(chows);
+    let nums = [c[0].1, c[1].1, c[2].1];
+    if c.iter().all(|t| t.0 == c[0].0) {
+        let step = nums[1] - nums[0];
+        let shifted = nums[2] - nums[1] == step;
+        if step == 0 && shifted {
+            Some(PURE_TRIPLE_CHOW)
+        } else if nums == [1, 4, 7] {
+            Some(PURE_STRAIGHT)
+        } else if (step == 1 || step == 2) && shifted {
+            Some(PURE_SHIFTED_CHOWS)
+        } else {
+            None
+        }
+    } else if c[0].0 != c[1].0 && c[1].0 != c[2].0 {
+        let mut nums = nums;
+        nums.sort();
+        if nums[0] == nums[2] {
+            Some(MIXED_TRIPLE_CHOW)
+        } else if nums == [1, 4, 7] {
+            Some(MIXED_STRAIGHT)
+        } else if nums[1] == nums[0] + 1 && nums[2] == nums[1] + 1 {
+            Some(MIXED_SHIFTED_CHOWS)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+// 4つの順子の組み合わせ
+fn chow_quad_fan(chows: &[Tile], pair: Option<Tile>) -> Option<usize> {
+    let c = sorted(chows);
+    let nums = [c[0].1, c[1].1, c[2].1, c[3].1];
+    if c.iter().all(|t| t.0 == c[0].0) {
+        let step = nums[1] - nums[0];
+        if nums.windows(2).all(|w| w[1] - w[0] == step) {
+            return match step {
+                0 => Some(QUADRUPLE_CHOW),
+                1 | 2 => Some(FOUR_PURE_SHIFTED_CHOWS),
+                _ => None,
+            };
+        }
+        if nums == [1, 1, 7, 7] && pair == Some(Tile(c[0].0, 5)) {
+            return Some(PURE_TERMINAL_CHOWS);
+        }
+    } else if nums == [1, 7, 1, 7] && c[0].0 == c[1].0 && c[2].0 == c[3].0 {
+        if let Some(p) = pair {
+            if p.is_suited() && p.1 == 5 && p.0 != c[0].0 && p.0 != c[2].0 {
+                return Some(THREE_SUITED_TERMINAL_CHOWS);
+            }
+        }
+    }
+    None
+}
+
+// 順子の組み合わせの番種 (一つの順子は同じ組み合わせで二度使わない)
+fn chow_fans(chows: &[Tile], pair: Option<Tile>) -> Vec<usize> {
+    if chows.len() == 4 {
+        if let Some(id) = chow_quad_fan(chows, pair) {
+            return vec![id];
+        }
+    }
+
+    // 3つの組み合わせ + 残りの順子と使った順子の組み合わせ
+    let mut best: Option<(usize, Option<usize>)> = None;
+    for skip in 0..chows.len() {
+        if chows.len() == 3 && skip > 0 {
+            break;
+        }
+        let used: Vec<Tile> = if chows.len() == 4 {
+            (0..4).filter(|&i| i != skip).map(|i| chows[i]).collect()
+        } else {
+            chows.to_vec()
+        };
+        if used.len() != 3 {
+            break;
+        }
+        let Some(id) = chow_triple_fan(&used) else {
+            continue;
+        };
+        let rest = (chows.len() == 4)
+            .then(|| {
+                used.iter()
+                    .filter_map(|&u| chow_pair_fan(chows[skip], u))
+                    .max_by_key(|&id| mcr_fan_value(id))
+            })
+            .flatten();
+        let value =
+            |(id, rest): (usize, Option<usize>)| mcr_fan_value(id) + rest.map_or(0, mcr_fan_value);
+        if best.is_none_or(|b| value((id, rest)) > value(b)) {
+            best = Some((id, rest));
+        }
+    }
+    if let Some((id, rest)) = best {
+        return std::iter::once(id).chain(rest).collect();
+    }
+
+    // 2つの組み合わせ (組み合わせが閉路にならない範囲で番数の高いものから)
+    let mut pairs = vec![];
+    for i in 0..chows.len() {
+        for j in i + 1..chows.len() {
+            if let Some(id) = chow_pair_fan(chows[i], chows[j]) {
+                pairs.push((id, i, j));
+            }
+        }
+    }
+    pairs.sort_by_key(|&(id, _, _)| std::cmp::Reverse(mcr_fan_value(id)));
+    let mut group: Vec<usize> = (0..chows.len()).collect();
+    let mut fans = vec![];
+    for (id, i, j) in pairs {
+        let (gi, gj) = (group[i], group[j]);
+        if gi == gj {
+            continue;
+        }
+        for g in group.iter_mut() {
+            if *g == gj {
+                *g = gi;
+            }
+        }
+        fans.push(id);
+    }
+    fans
+}
+
+// 3つの刻子の組み合わせ (一色三节高, 三同刻, 三色三节高)
+fn pung_triple_fan(pungs: &[Tile]) -> Option<usize> {
+    let p = sorted(pungs);
+    if !p.iter().all(|t| t.is_suited()) {
+        return None;
+    }
+    if p.iter().all(|t| t.0 == p[0].0) {
+        (p[1].1 == p[0].1 + 1 && p[2].1 == p[1].1 + 1).then_some(PURE_SHIFTED_PUNGS)
+    } else if p[0].0 != p[1].0 && p[1].0 != p[2].0 {
+        let mut nums = [p[0].1, p[1].1, p[2].1];
+        nums.sort();
+        if nums[0] == nums[2] {
+            Some(TRIPLE_PUNG)
+        } else if nums[1] == nums[0] + 1 && nums[2] == nums[1] + 1 {
+            Some(MIXED_SHIFTED_PUNGS)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+// 刻子の組み合わせの番種
+fn pung_fans(pungs: &[Tile]) -> Vec<usize> {
+    let suited: Vec<Tile> = sorted(pungs)
+        .into_iter()
+        .filter(|t| t.is_suited())
+        .collect();
+    if suited.len() == 4
+        && suited.iter().all(|t| t.0 == suited[0].0)
+        && suited.windows(2).all(|w| w[1].1 == w[0].1 + 1)
+    {
+        return vec![FOUR_PURE_SHIFTED_PUNGS];
+    }
+
+    let double_pung = |a: Tile, b: Tile| a.0 != b.0 && a.1 == b.1;
+    let mut best: Option<(usize, usize)> = None;
+    if suited.len() >= 3 {
+        for skip in 0..suited.len() {
+            let used: Vec<Tile> = (0..suited.len())
+                .filter(|&i| i != skip || suited.len() == 3)
+                .map(|i| suited[i])
+                .collect();
+            let Some(id) = pung_triple_fan(&used) else {
+                continue;
+            };
+            let rest =
+                (suited.len() == 4 && used.iter().any(|&u| double_pung(suited[skip], u))) as usize;
+            if best.is_none_or(|(b, _)| mcr_fan_value(id) > mcr_fan_value(b)) {
+                best = Some((id, rest));
+            }
+        }
+    }
+    if let Some((id, rest)) = best {
+        let mut fans = vec![id];
+        fans.extend(std::iter::repeat_n(DOUBLE_PUNG, rest));
+        return fans;
+    }
+
+    let mut fans = vec![];
+    for i in 0..suited.len() {
+        for j in i + 1..suited.len() {
+            if double_pung(suited[i], suited[j]) {
+                fans.push(DOUBLE_PUNG);
+            }
+        }
+    }
+    fans
+}
+
+// 字牌・幺九牌の刻子の番種
+fn honor_pung_fans(ctx: &HandContext, pungs: &[Tile], pair: Option<Tile>) -> Vec<usize> {
+    let mut fans = vec![];
+    let dragons = pungs.iter().filter(|t| t.is_dragon()).count();
+    let winds = pungs.iter().filter(|t| t.is_wind()).count();
+    let pair_dragon = pair.is_some_and(|p| p.is_dragon());
+    let pair_wind = pair.is_some_and(|p| p.is_wind());
+
+    match dragons {
+        3 => fans.push(BIG_THREE_DRAGONS),
+        2 if pair_dragon => fans.push(LITTLE_THREE_DRAGONS),
+        2 => fans.push(TWO_DRAGON_PUNGS),
+        _ => {}
+    }
+    fans.extend(std::iter::repeat_n(DRAGON_PUNG, dragons));
+    match winds {
+        4 => fans.push(BIG_FOUR_WINDS),
+        3 if pair_wind => fans.push(LITTLE_FOUR_WINDS),
+        3 => fans.push(BIG_THREE_WINDS),
+        _ => {}
+    }
+    for t in pungs {
+        if t.is_wind() {
+            if t.1 == ctx.bakaze {
+                fans.push(PREVALENT_WIND);
+            }
+            if t.1 == ctx.jikaze {
+                fans.push(SEAT_WIND);
+            }
+            // 三風刻以上の風牌の刻子は幺九刻に数えない
+            if winds < 3 && t.1 != ctx.bakaze && t.1 != ctx.jikaze {
+                fans.push(PUNG_OF_TERMINALS_OR_HONORS);
+            }
+        } else if t.is_terminal() {
+            fans.push(PUNG_OF_TERMINALS_OR_HONORS);
+        }
+    }
+    fans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fan_ids(res: &McrResult) -> Vec<(usize, usize)> {
+        res.fans.iter().map(|(f, n)| (f.id, *n)).collect()
+    }
+
+    #[test]
+    fn score_mcr_works_01() {
+        // 无番和
+        let ctx = test_ctx("56s22z+7s (chi 234m) (chi 678p) (pon 888s)", false, WSO);
+        let res = score_mcr(&ctx, &McrRules::default()).unwrap();
+        assert_eq!(fan_ids(&res), vec![(43, 1)]);
+        assert_eq!((res.fan, res.total), (8, 8));
+    }
+
+    #[test]
+    fn score_mcr_works_02() {
+        // 8番未満 (门前清, 单钓将)
+        let ctx = test_ctx("123m456p678s345m1z+1z", false, WSO);
+        assert_eq!(
+            score_mcr(&ctx, &McrRules::default()).unwrap_err(),
+            MahjongError::InsufficientFan(8, 3)
+        );
+        let rules = McrRules {
+            min_fan: 0,
+            ..McrRules::default()
+        };
+        let res = score_mcr(&ctx, &rules).unwrap();
+        assert_eq!(fan_ids(&res), vec![(62, 1), (79, 1)]);
+    }
+
+    #[test]
+    fn score_mcr_works_03() {
+        // 三色三同顺 + 老少副 (残りの順子は一度だけ組み合わせる), 全带幺
+        let ctx = test_ctx("123m123p123s789m1z+1z", false, WSO);
+        let res = score_mcr(&ctx, &McrRules::default()).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![(41, 1), (55, 1), (62, 1), (72, 1), (79, 1)]
+        );
+        assert_eq!(res.fan, 16);
+    }
+
+    #[test]
+    fn score_mcr_works_04() {
+        // 七对と花牌 (花牌は8番縛りに含まない)
+        let ctx = test_ctx("1122m3344p5566s7z+7z 12h", false, WSO);
+        let res = score_mcr(&ctx, &McrRules::default()).unwrap();
+        assert_eq!(fan_ids(&res), vec![(19, 1), (81, 2)]);
+        assert_eq!((res.fan, res.flower_fan, res.total), (24, 2, 26));
+    }
+
+    #[test]
+    fn score_mcr_works_05() {
+        // 九莲宝灯 + 双暗刻 (清一色, 门前清, 幺九刻, 无字は計上しない)
+        let ctx = test_ctx("1112345678999m+5m", false, WSO);
+        let res = score_mcr(&ctx, &McrRules::default()).unwrap();
+        assert_eq!(fan_ids(&res), vec![(4, 1), (66, 1)]);
+    }
+
+    #[test]
+    fn score_mcr_works_06() {
+        // 全不靠, 组合龙
+        let ctx = test_ctx("147m258p369s1235z+6z", false, WSO);
+        let res = score_mcr(&ctx, &McrRules::default()).unwrap();
+        assert_eq!(fan_ids(&res), vec![(34, 1), (35, 1)]);
+        assert_eq!(res.fan, 24);
+    }
+
+    #[test]
+    fn score_mcr_works_07() {
+        // 嵌张は待ち牌が1種類の場合のみ, 和绝张は和了ごとのフラグで指定する
+        let rules = McrRules {
+            min_fan: 0,
+            ..McrRules::default()
+        };
+        let mut ctx = test_ctx("123m456p789s13s11z+2s", false, WSO);
+        ctx.yaku_flags.last_tile = true;
+        let res = score_mcr(&ctx, &rules).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![(39, 1), (58, 1), (62, 1), (72, 1), (78, 1)]
+        );
+
+        // 1113s は 2s と 3s の2種類の待ち
+        let mut ctx = test_ctx("123m456p789s1113s+2s", false, WSO);
+        ctx.yaku_flags.last_tile = true;
+        let res = score_mcr(&ctx, &rules).unwrap();
+        assert_eq!(
+            fan_ids(&res),
+            vec![(39, 1), (58, 1), (62, 1), (63, 1), (72, 1)]
+        );
+    }
+
+    #[test]
+    fn get_payments_mcr_works_01() {
+        assert_eq!(get_payments_mcr(10, 0, Some(2)), Ok(vec![34, -8, -18, -8]));
+        assert_eq!(get_payments_mcr(10, 1, None), Ok(vec![-18, 54, -18, -18]));
+        assert_eq!(
+            get_payments_mcr(10, 1, Some(1)),
+            Err(MahjongError::InvalidSeat(1))
+        );
+    }
+}
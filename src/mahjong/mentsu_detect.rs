@@ -9,19 +9,10 @@ trait NumCntTableTrait {
 
 impl NumCntTableTrait for NumCntTable {
     fn cnt(&self) -> usize {
-        let mut cnt = 0;
-        for i in 0..10 {
-            cnt += self[i];
-        }
-        cnt
+        self.iter().sum()
     }
     fn get_min(&self) -> Option<usize> {
-        for i in 0..10 {
-            if self[i] > 0 {
-                return Some(i);
-            }
-        }
-        None
+        (0..10).find(|&i| self[i] > 0)
     }
 }
 
@@ -54,7 +45,7 @@ pub enum MentsuType {
 }
 
 pub fn get_mentsu(table: &NumCntTable) -> Option<Vec<Vec<Mentsu>>> {
-    if table.cnt() % 3 != 0 {
+    if !table.cnt().is_multiple_of(3) {
         return None;
     }
 
@@ -70,7 +61,7 @@ pub fn get_mentsu(table: &NumCntTable) -> Option<Vec<Vec<Mentsu>>> {
         }
         let mut output: Vec<Vec<Mentsu>> = Vec::new();
         if table[i] >= 3 {
-            let mut table = table.clone();
+            let mut table = *table;
             table[i] -= 3;
 
             if let Some(v) = get_mentsu(&table) {
@@ -82,7 +73,7 @@ pub fn get_mentsu(table: &NumCntTable) -> Option<Vec<Vec<Mentsu>>> {
             }
         }
         if i < 8 && table[i] >= 1 && table[i + 1] >= 1 && table[i + 2] >= 1 {
-            let mut table = table.clone();
+            let mut table = *table;
             table[i] -= 1;
             table[i + 1] -= 1;
             table[i + 2] -= 1;
@@ -102,7 +93,7 @@ pub fn get_mentsu(table: &NumCntTable) -> Option<Vec<Vec<Mentsu>>> {
 }
 
 pub fn get_koutsu(table: &NumCntTable) -> Option<Vec<Vec<Mentsu>>> {
-    if table.cnt() % 3 != 0 {
+    if !table.cnt().is_multiple_of(3) {
         return None;
     }
 
@@ -116,7 +107,7 @@ pub fn get_koutsu(table: &NumCntTable) -> Option<Vec<Vec<Mentsu>>> {
         }
         let mut output: Vec<Vec<Mentsu>> = Vec::new();
         if table[i] >= 3 {
-            let mut table = table.clone();
+            let mut table = *table;
             table[i] -= 3;
 
             if let Some(v) = get_mentsu(&table) {
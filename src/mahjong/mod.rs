@@ -1,9 +1,20 @@
 pub mod define;
 pub mod dora;
+pub mod error;
+pub mod fan;
+pub mod fu;
 pub mod hand;
+pub mod hk;
+pub mod machi;
+pub mod mcr;
 pub mod mentsu_detect;
 pub mod point;
+pub mod rules;
+pub mod score;
+pub mod shanten;
 pub mod tiles;
+pub mod tw;
+pub mod ukeire;
 pub mod win;
 pub mod yaku;
 
@@ -11,9 +22,20 @@ use std::fmt;
 
 pub use define::*;
 pub use dora::*;
+pub use error::*;
+pub use fan::*;
+pub use fu::*;
 pub use hand::*;
+pub use hk::*;
+pub use machi::*;
+pub use mcr::*;
 pub use mentsu_detect::*;
 pub use point::*;
+pub use rules::*;
+pub use score::*;
+pub use shanten::*;
 pub use tiles::*;
+pub use tw::*;
+pub use ukeire::*;
 pub use win::*;
 pub use yaku::*;
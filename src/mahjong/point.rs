@@ -1,68 +1,28 @@
 use super::*;
+use serde::{Deserialize, Serialize};
 
-// 親が他家を直撃した場合の点数表 (役満未満)
-const POINT_DEALER: [[Point; 11]; 13] = [
-    [0; 11],                                                      // 0飜
-    [0, 0, 1500, 2000, 2400, 2900, 3400, 3900, 4400, 4800, 5300], // 1飜
-    [
-        2000, 2400, 2900, 3900, 4800, 5800, 6800, 7700, 8700, 9600, 10600,
-    ], // 2飜
-    [
-        3900, 4800, 5800, 7700, 9600, 11600, 12000, 12000, 12000, 12000, 12000,
-    ], // 3飜
-    [
-        7700, 9600, 11600, 12000, 12000, 12000, 12000, 12000, 12000, 12000, 12000,
-    ], // 4飜
-    [12000; 11],                                                  // 5飜
-    [18000; 11],                                                  // 6飜
-    [18000; 11],                                                  // 7飜
-    [24000; 11],                                                  // 8飜
-    [24000; 11],                                                  // 9飜
-    [24000; 11],                                                  // 10飜
-    [36000; 11],                                                  // 11飜
-    [36000; 11],                                                  // 12飜
-];
-
-// 子が他家を直撃した場合の点数表 (役満未満)
-const POINT_NON_DEALER: [[Point; 11]; 13] = [
-    [0; 11],                                                      // 0飜
-    [0, 0, 1000, 1300, 1600, 2000, 2300, 2600, 2900, 3200, 3600], // 1飜
-    [
-        1300, 1600, 2000, 2600, 3200, 3900, 4500, 5200, 5800, 6400, 7100,
-    ], // 2飜
-    [
-        2600, 3200, 3900, 5200, 6400, 7700, 8000, 8000, 8000, 8000, 8000,
-    ], // 3飜
-    [
-        5200, 6400, 7700, 8000, 8000, 8000, 8000, 8000, 8000, 8000, 8000,
-    ], // 4飜
-    [8000; 11],                                                   // 5飜
-    [12000; 11],                                                  // 6飜
-    [12000; 11],                                                  // 7飜
-    [16000; 11],                                                  // 8飜
-    [16000; 11],                                                  // 9飜
-    [16000; 11],                                                  // 10飜
-    [24000; 11],                                                  // 11飜
-    [24000; 11],                                                  // 12飜
-];
-
-const POINT_YAKUMAN_DEALER: Point = 48000;
-const POINT_YAKUMAN_NON_DEALER: Point = 32000;
-
-fn calc_fu_index(fu: usize) -> usize {
-    match fu {
-        20 => 0,
-        25 => 1,
-        30 => 2,
-        40 => 3,
-        50 => 4,
-        60 => 5,
-        70 => 6,
-        80 => 7,
-        90 => 8,
-        100 => 9,
-        110 => 10,
-        _ => panic!("invalid fu number"),
+pub const POINT_MANGAN: Point = 2000; // 満貫の基本点
+pub const POINT_YAKUMAN: Point = 8000; // 役満の基本点
+
+pub const POINT_HONBA: Point = 100; // 1本場あたりの支払う人1人の加算 (ロンの場合は他家の人数分)
+pub const POINT_RIICHI_STICK: Point = 1000; // 供託のリーチ棒1本
+
+// 点数計算のルール設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PointRules {
+    pub kiriage_mangan: bool,   // 切り上げ満貫 (4飜30符, 3飜60符を満貫とする)
+    pub kazoe_yakuman: bool,    // 13飜以上を数え役満とする (false の場合は三倍満止まり)
+    pub multiple_yakuman: bool, // 役満の複合・ダブル役満を認める (false の場合は役満止まり)
+}
+
+impl Default for PointRules {
+    fn default() -> Self {
+        Self {
+            kiriage_mangan: false,
+            kazoe_yakuman: true,
+            multiple_yakuman: true,
+        }
     }
 }
 
@@ -70,72 +30,113 @@ fn ceil100(n: Point) -> Point {
     (n + 99) / 100 * 100
 }
 
-// 親の和了 (直撃, ツモ和了の子, ツモ和了の親)の支払いを返却
-fn get_points_dealer(fu: usize, fan: usize) -> Points {
-    let fu_index = calc_fu_index(fu);
-    let point = if fan < 13 {
-        POINT_DEALER[fan][fu_index]
-    } else {
-        POINT_YAKUMAN_DEALER
-    };
-    (point, ceil100(point / 3), 0)
-}
+// 基本点 (子のツモ和了での子1人あたりの支払いの切り上げ前) を返却
+pub fn get_base_points(
+    fu: usize,
+    fan: usize,
+    yakuman_count: usize,
+    rules: &PointRules,
+) -> Result<Point, MahjongError> {
+    if yakuman_count > 0 {
+        let mag = if rules.multiple_yakuman {
+            yakuman_count
+        } else {
+            1
+        };
+        return Ok(POINT_YAKUMAN * mag as Point);
+    }
 
-// 子の和了 (直撃, ツモ和了の子, ツモ和了の親)の支払いを返却
-fn get_points_non_dealer(fu: usize, fan: usize) -> Points {
-    let fu_index = calc_fu_index(fu);
-    let point = if fan < 13 {
-        POINT_NON_DEALER[fan][fu_index]
-    } else {
-        POINT_YAKUMAN_NON_DEALER
+    let base = match fan {
+        0 => return Err(MahjongError::NoYaku),
+        1..=4 => {
+            if fu != 25 && (fu < 20 || !fu.is_multiple_of(10)) {
+                return Err(MahjongError::InvalidFu(fu));
+            }
+            let base = (fu as Point) << (fan + 2); // 符 × 2^(飜+2)
+            if base >= POINT_MANGAN || (rules.kiriage_mangan && base >= 1920) {
+                POINT_MANGAN
+            } else {
+                base
+            }
+        }
+        5 => POINT_MANGAN,
+        6 | 7 => POINT_MANGAN * 3 / 2,
+        8..=10 => POINT_MANGAN * 2,
+        11 | 12 => POINT_MANGAN * 3,
+        _ => {
+            if rules.kazoe_yakuman {
+                POINT_YAKUMAN
+            } else {
+                POINT_MANGAN * 3
+            }
+        }
     };
-    (point, ceil100(point / 4), ceil100(point / 2))
-}
-
-// 親の役満 (直撃, ツモ和了の子, ツモ和了の親)の支払いを返却
-fn get_points_dealer_yakuman(mag: usize) -> Points {
-    let s = POINT_YAKUMAN_DEALER * mag as i32;
-    (s, s / 3, 0)
-}
-
-// 子の役満 (直撃, ツモ和了の子, ツモ和了の親)の支払いを返却
-fn get_points_non_dealer_yakuman(mag: usize) -> Points {
-    let s = POINT_YAKUMAN_NON_DEALER * mag as i32;
-    (s, s / 4, s / 2)
+    Ok(base)
 }
 
-pub fn get_points(is_dealer: bool, fu: usize, fan: usize, yakuman_count: usize) -> Points {
+// (直撃, ツモ和了の子, ツモ和了の親)の支払いを返却. 親の和了の場合, ツモ和了の親の支払いは0
+pub fn get_points(
+    is_dealer: bool,
+    fu: usize,
+    fan: usize,
+    yakuman_count: usize,
+    rules: &PointRules,
+) -> Result<Points, MahjongError> {
+    let base = get_base_points(fu, fan, yakuman_count, rules)?;
     if is_dealer {
-        if yakuman_count > 0 {
-            get_points_dealer_yakuman(yakuman_count)
-        } else {
-            get_points_dealer(fu, fan)
-        }
+        Ok((ceil100(base * 6), ceil100(base * 2), 0))
     } else {
-        if yakuman_count > 0 {
-            get_points_non_dealer_yakuman(yakuman_count)
-        } else {
-            get_points_non_dealer(fu, fan)
-        }
+        Ok((ceil100(base * 4), ceil100(base), ceil100(base * 2)))
     }
 }
 
-pub fn get_score_title(fu: usize, fan: usize, yakuman_count: usize) -> String {
-    match yakuman_count {
-        0 => {
-            if fan >= 13 {
-                "数え役満"
+// 三人麻雀の (直撃, ツモ和了の子, ツモ和了の親)の支払いを返却
+pub fn get_points_sanma(
+    is_dealer: bool,
+    fu: usize,
+    fan: usize,
+    yakuman_count: usize,
+    rules: &PointRules,
+    tsumo: SanmaTsumo,
+) -> Result<Points, MahjongError> {
+    match tsumo {
+        SanmaTsumo::TsumoLoss => get_points(is_dealer, fu, fan, yakuman_count, rules),
+        SanmaTsumo::NorthBisection => {
+            // 北家が支払うはずだった分 (基本点 or 2倍) を折半して上乗せ
+            let base = get_base_points(fu, fan, yakuman_count, rules)?;
+            if is_dealer {
+                Ok((ceil100(base * 6), ceil100(base * 3), 0))
             } else {
-                let fu_index = calc_fu_index(fu);
-                match POINT_NON_DEALER[fan][fu_index] {
-                    8000 => "満貫",
-                    12000 => "跳満",
-                    16000 => "倍満",
-                    24000 => "三倍満",
-                    _ => "",
-                }
+                Ok((
+                    ceil100(base * 4),
+                    ceil100(base * 3 / 2),
+                    ceil100(base * 5 / 2),
+                ))
             }
         }
+    }
+}
+
+pub fn get_score_title(
+    fu: usize,
+    fan: usize,
+    yakuman_count: usize,
+    rules: &PointRules,
+) -> Result<String, MahjongError> {
+    let yakuman_count = if rules.multiple_yakuman {
+        yakuman_count
+    } else {
+        yakuman_count.min(1)
+    };
+    let title = match yakuman_count {
+        0 => match get_base_points(fu, fan, 0, rules)? {
+            2000 => "満貫",
+            3000 => "跳満",
+            4000 => "倍満",
+            6000 => "三倍満",
+            8000 => "数え役満",
+            _ => "",
+        },
         1 => "役満",
         2 => "二倍役満",
         3 => "三倍役満",
@@ -143,7 +144,225 @@ pub fn get_score_title(fu: usize, fan: usize, yakuman_count: usize) -> String {
         5 => "五倍役満",
         6 => "六倍役満",
         7 => "七倍役満",
-        _ => panic!(),
+        _ => return Err(MahjongError::InvalidYakumanCount(yakuman_count)),
+    };
+    Ok(title.to_string())
+}
+
+// 座席番号が seats 人の範囲内で, 放銃者が和了者と異なるかをチェック
+pub(crate) fn check_seats(
+    seats: usize,
+    winner: Seat,
+    loser: Option<Seat>,
+) -> Result<(), MahjongError> {
+    for seat in std::iter::once(winner).chain(loser) {
+        if seat >= seats {
+            return Err(MahjongError::InvalidSeat(seat));
+        }
+    }
+    if loser == Some(winner) {
+        return Err(MahjongError::InvalidSeat(winner));
+    }
+    Ok(())
+}
+
+// 積み棒と供託を含めた各座席 (seats 人分) の点数の増減を返却
+// loser が None の場合はツモ和了, 供託のリーチ棒はすべて winner が受け取る
+// points は winner が親かどうか (winner == dealer) に合わせて計算したもの
+pub fn get_payments(
+    points: Points,
+    seats: usize,
+    winner: Seat,
+    dealer: Seat,
+    loser: Option<Seat>,
+    honba: usize,
+    riichi_sticks: usize,
+) -> Result<Vec<Point>, MahjongError> {
+    check_seats(seats, winner, loser)?;
+    check_seats(seats, dealer, None)?;
+    // 親の和了の点数はツモ・親の支払いが0
+    if (winner == dealer) != (points.2 == 0) {
+        return Err(MahjongError::DealerMismatch(winner));
+    }
+
+    let mut deltas = vec![0; seats];
+    let honba = honba as Point;
+    match loser {
+        Some(loser) => {
+            let p = points.0 + POINT_HONBA * (seats as Point - 1) * honba;
+            deltas[loser] -= p;
+            deltas[winner] += p;
+        }
+        None => {
+            let honba_each = POINT_HONBA * honba;
+            for seat in 0..seats {
+                if seat == winner {
+                    continue;
+                }
+                let p = if seat == dealer { points.2 } else { points.1 } + honba_each;
+                deltas[seat] -= p;
+                deltas[winner] += p;
+            }
+        }
+    }
+    deltas[winner] += POINT_RIICHI_STICK * riichi_sticks as Point;
+    Ok(deltas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_points_works_01() {
+        let rules = PointRules::default();
+        assert_eq!(get_points(false, 30, 1, 0, &rules), Ok((1000, 300, 500)));
+        assert_eq!(get_points(false, 20, 2, 0, &rules), Ok((1300, 400, 700)));
+        assert_eq!(get_points(false, 25, 3, 0, &rules), Ok((3200, 800, 1600)));
+        assert_eq!(get_points(true, 30, 2, 0, &rules), Ok((2900, 1000, 0)));
+        assert_eq!(get_points(true, 40, 3, 0, &rules), Ok((7700, 2600, 0)));
+        assert_eq!(get_points(false, 110, 2, 0, &rules), Ok((7100, 1800, 3600)));
+        assert_eq!(get_points(false, 30, 7, 0, &rules), Ok((12000, 3000, 6000)));
+        assert_eq!(
+            get_points(false, 35, 2, 0, &rules),
+            Err(MahjongError::InvalidFu(35))
+        );
+    }
+
+    #[test]
+    fn get_points_works_02() {
+        // 切り上げ満貫
+        let mut rules = PointRules::default();
+        assert_eq!(get_points(false, 30, 4, 0, &rules), Ok((7700, 2000, 3900)));
+        assert_eq!(get_points(true, 60, 3, 0, &rules), Ok((11600, 3900, 0)));
+        rules.kiriage_mangan = true;
+        assert_eq!(get_points(false, 30, 4, 0, &rules), Ok((8000, 2000, 4000)));
+        assert_eq!(get_points(true, 60, 3, 0, &rules), Ok((12000, 4000, 0)));
+        assert_eq!(get_score_title(30, 4, 0, &rules), Ok("満貫".to_string()));
+    }
+
+    #[test]
+    fn get_points_works_03() {
+        // 数え役満, 複合役満
+        let mut rules = PointRules::default();
+        assert_eq!(
+            get_points(false, 30, 13, 0, &rules),
+            Ok((32000, 8000, 16000))
+        );
+        assert_eq!(get_points(true, 0, 0, 2, &rules), Ok((96000, 32000, 0)));
+        assert_eq!(get_score_title(0, 0, 2, &rules), Ok("二倍役満".to_string()));
+        rules.kazoe_yakuman = false;
+        rules.multiple_yakuman = false;
+        assert_eq!(
+            get_points(false, 30, 13, 0, &rules),
+            Ok((24000, 6000, 12000))
+        );
+        assert_eq!(get_score_title(30, 13, 0, &rules), Ok("三倍満".to_string()));
+        assert_eq!(get_points(true, 0, 0, 2, &rules), Ok((48000, 16000, 0)));
+        assert_eq!(get_score_title(0, 0, 2, &rules), Ok("役満".to_string()));
+    }
+
+    #[test]
+    fn get_payments_works_01() {
+        // 子の3900ロン, 2本場, 供託1本
+        let points = get_points(false, 30, 3, 0, &PointRules::default()).unwrap();
+        assert_eq!(
+            get_payments(points, SEAT, 1, 0, Some(2), 2, 1),
+            Ok(vec![0, 5500, -4500, 0])
+        );
+    }
+
+    #[test]
+    fn get_payments_works_02() {
+        // 子の1000/2000ツモ, 1本場
+        let points = get_points(false, 30, 3, 0, &PointRules::default()).unwrap();
+        assert_eq!(
+            get_payments(points, SEAT, 2, 0, None, 1, 0),
+            Ok(vec![-2100, -1100, 4300, -1100])
+        );
+    }
+
+    #[test]
+    fn get_payments_works_03() {
+        // 親の3900オールツモ, 3本場, 供託2本
+        let points = get_points(true, 30, 4, 0, &PointRules::default()).unwrap();
+        assert_eq!(
+            get_payments(points, SEAT, 0, 0, None, 3, 2),
+            Ok(vec![14600, -4200, -4200, -4200])
+        );
+    }
+
+    #[test]
+    fn get_payments_works_04() {
+        // 三人麻雀 ツモ損: 子の1000/2000ツモ, 1本場
+        let points = get_points_sanma(
+            false,
+            30,
+            3,
+            0,
+            &PointRules::default(),
+            SanmaTsumo::TsumoLoss,
+        )
+        .unwrap();
+        assert_eq!(
+            get_payments(points, SEAT_SANMA, 1, 0, None, 1, 0),
+            Ok(vec![-2100, 3200, -1100])
+        );
+    }
+
+    #[test]
+    fn get_payments_works_05() {
+        // 三人麻雀 北家折半: 子の満貫ツモ (2000/4000 → 3000/5000), ロンの本場は2人分
+        let points = get_points_sanma(
+            false,
+            30,
+            5,
+            0,
+            &PointRules::default(),
+            SanmaTsumo::NorthBisection,
+        )
+        .unwrap();
+        assert_eq!(points, (8000, 3000, 5000));
+        assert_eq!(
+            get_payments(points, SEAT_SANMA, 1, 0, None, 0, 0),
+            Ok(vec![-5000, 8000, -3000])
+        );
+        assert_eq!(
+            get_payments(points, SEAT_SANMA, 1, 0, Some(2), 1, 0),
+            Ok(vec![0, 8200, -8200])
+        );
+    }
+
+    #[test]
+    fn get_payments_works_06() {
+        // 座席番号が人数を超える場合, 和了者と放銃者が同じ場合
+        let points = get_points(false, 30, 3, 0, &PointRules::default()).unwrap();
+        assert_eq!(
+            get_payments(points, SEAT_SANMA, 3, 0, None, 0, 0),
+            Err(MahjongError::InvalidSeat(3))
+        );
+        assert_eq!(
+            get_payments(points, SEAT, 1, 4, None, 0, 0),
+            Err(MahjongError::InvalidSeat(4))
+        );
+        assert_eq!(
+            get_payments(points, SEAT, 1, 0, Some(1), 0, 0),
+            Err(MahjongError::InvalidSeat(1))
+        );
+    }
+
+    #[test]
+    fn get_payments_works_07() {
+        // 子の点数を親の和了に, 親の点数を子の和了に使った場合
+        let points = get_points(false, 30, 3, 0, &PointRules::default()).unwrap();
+        assert_eq!(
+            get_payments(points, SEAT, 0, 0, Some(2), 0, 0),
+            Err(MahjongError::DealerMismatch(0))
+        );
+        let points = get_points(true, 30, 3, 0, &PointRules::default()).unwrap();
+        assert_eq!(
+            get_payments(points, SEAT, 1, 0, None, 0, 0),
+            Err(MahjongError::DealerMismatch(1))
+        );
     }
-    .to_string()
 }
@@ -0,0 +1,347 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+// ローカル役満の役ID (大車輪, 大竹林, 大数隣, 大七星)
+pub const LOCAL_YAKUMAN: &[usize] = &[55, 56, 57, 58];
+
+// 点数計算のルール設定
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ruleset {
+    pub disabled_yaku: BTreeSet<usize>,     // 無効にする役ID
+    pub yaku_fan: BTreeMap<usize, YakuFan>, // 役IDごとの飜の変更 (13以上は役満)
+    pub kuitan: bool,                       // 喰いタン (鳴いた断幺九) を認める
+    pub atozuke: bool,                      // 後付け (待ちの一部でしか役がない和了) を認める
+    pub shibari: Option<ShibariRules>,      // 縛り (None の場合は1飜縛り)
+    pub local_yakuman: bool,                // ローカル役満を認める
+    pub double_yakuman: bool, // 純正九蓮宝燈, 四暗刻単騎などをダブル役満とする (false の場合は役満)
+    pub red_fives: RedFives,  // 赤五の枚数の上限
+    pub fu: FuRules,          // 符計算
+    pub points: PointRules,   // 点数計算
+    pub flower: FlowerRules,  // 花牌
+    pub sanma: Option<SanmaRules>, // 三人麻雀 (None の場合は四人麻雀)
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self {
+            disabled_yaku: BTreeSet::new(),
+            yaku_fan: BTreeMap::new(),
+            kuitan: true,
+            atozuke: true,
+            shibari: None,
+            local_yakuman: true,
+            double_yakuman: true,
+            red_fives: RedFives::default(),
+            fu: FuRules::default(),
+            points: PointRules::default(),
+            flower: FlowerRules::default(),
+            sanma: None,
+        }
+    }
+}
+
+impl Ruleset {
+    // 天鳳の四人麻雀 (赤あり, ローカル役満・ダブル役満なし)
+    pub fn tenhou() -> Self {
+        Self {
+            local_yakuman: false,
+            double_yakuman: false,
+            red_fives: RedFives { m: 1, p: 1, s: 1 },
+            ..Self::default()
+        }
+    }
+
+    // 天鳳の三人麻雀 (北抜き, ツモ損)
+    pub fn tenhou_sanma() -> Self {
+        Self {
+            red_fives: RedFives { m: 0, p: 1, s: 1 },
+            sanma: Some(SanmaRules::default()),
+            ..Self::tenhou()
+        }
+    }
+
+    // 競技ルール (赤なし, 後付けなし, 切り上げ満貫, 役満の複合なし)
+    pub fn competition() -> Self {
+        Self {
+            atozuke: false,
+            local_yakuman: false,
+            double_yakuman: false,
+            red_fives: RedFives { m: 0, p: 0, s: 0 },
+            points: PointRules {
+                kiriage_mangan: true,
+                multiple_yakuman: false,
+                ..PointRules::default()
+            },
+            ..Self::default()
+        }
+    }
+
+    pub fn from_toml(s: &str) -> Result<Self, MahjongError> {
+        toml::from_str(s).map_err(|e| MahjongError::InvalidRuleset(e.to_string()))
+    }
+
+    pub fn from_json(s: &str) -> Result<Self, MahjongError> {
+        serde_json::from_str(s).map_err(|e| MahjongError::InvalidRuleset(e.to_string()))
+    }
+
+    // 拡張子 (.toml または .json) で形式を判断して読み込む
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MahjongError> {
+        let path = path.as_ref();
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| MahjongError::InvalidRuleset(e.to_string()))?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml(&s),
+            Some("json") => Self::from_json(&s),
+            _ => Err(MahjongError::InvalidRuleset(path.display().to_string())),
+        }
+    }
+
+    // 役が有効か
+    pub fn is_yaku_enabled(&self, id: usize) -> bool {
+        !self.disabled_yaku.contains(&id) && (self.local_yakuman || !LOCAL_YAKUMAN.contains(&id))
+    }
+
+    // 役の (鳴きなし, 鳴きあり) の飜
+    pub fn fan_of(&self, yaku: &Yaku) -> (usize, usize) {
+        let YakuFan { close, open } = self.yaku_fan.get(&yaku.id).copied().unwrap_or(YakuFan {
+            close: yaku.fan_close,
+            open: yaku.fan_open,
+        });
+        if self.double_yakuman {
+            (close, open)
+        } else {
+            (close.min(13), open.min(13))
+        }
+    }
+
+    // 本場に応じた和了に必要な役の飜 (ドラを含まない)
+    pub fn min_fan(&self, honba: usize) -> usize {
+        match &self.shibari {
+            Some(s) if honba >= s.from_honba => s.fan,
+            _ => 1,
+        }
+    }
+
+    // 座席の数
+    pub fn seats(&self) -> usize {
+        if self.sanma.is_some() {
+            SEAT_SANMA
+        } else {
+            SEAT
+        }
+    }
+
+    // ルール上使用する牌か (三人麻雀では萬子の2~8を使わない)
+    pub fn is_available(&self, tile: &Tile) -> bool {
+        self.sanma.is_none() || !(tile.0 == TM && tile.is_simple())
+    }
+
+    // 使用しない牌, 赤五の枚数をチェック
+    pub fn check_tiles(&self, ctx: &HandContext) -> Result<(), MahjongError> {
+        let mut red = [0; TYPE];
+        let fuuro_tiles = ctx.fuuro.iter().flat_map(|Fuuro(_, v)| v);
+        let tiles = ctx
+            .hand_tiles
+            .iter()
+            .chain(fuuro_tiles)
+            .chain(std::iter::once(&ctx.agari_tile))
+            .chain(&ctx.nuki_dora);
+        for TileWithDora(t, d) in tiles {
+            if !self.is_available(t) {
+                return Err(MahjongError::UnavailableTile(*t));
+            }
+            if t.is_suited() && t.1 == 5 && *d > 0 {
+                red[t.0] += 1;
+                if red[t.0] > self.red_fives.get(t.0) {
+                    return Err(MahjongError::TooManyRedFives(*t));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// 点数計算の方式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ScoringMode {
+    Riichi(Ruleset),    // リーチ麻雀
+    Mcr(McrRules),      // 中国麻将 (国標麻将)
+    HongKong(HkRules),  // 香港麻雀 (古典ルール)
+    Taiwanese(TwRules), // 台湾麻雀 (16枚, HandContext の mentsu は5)
+}
+
+impl Default for ScoringMode {
+    fn default() -> Self {
+        ScoringMode::Riichi(Ruleset::default())
+    }
+}
+
+// 役の飜の設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YakuFan {
+    pub close: usize, // 鳴きなしの飜
+    pub open: usize,  // 鳴きありの飜 (0 の場合は鳴いたら不成立)
+}
+
+// 縛りの設定 (例: 5本場から2飜縛り)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShibariRules {
+    pub fan: usize,        // 必要な役の飜
+    pub from_honba: usize, // 縛りが適用される本場
+}
+
+impl Default for ShibariRules {
+    fn default() -> Self {
+        Self {
+            fan: 2,
+            from_honba: 5,
+        }
+    }
+}
+
+// 赤五の枚数の上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedFives {
+    pub m: usize, // 萬子
+    pub p: usize, // 筒子
+    pub s: usize, // 索子
+}
+
+impl Default for RedFives {
+    fn default() -> Self {
+        Self {
+            m: TILE,
+            p: TILE,
+            s: TILE,
+        }
+    }
+}
+
+impl RedFives {
+    pub fn get(&self, t: Type) -> usize {
+        match t {
+            TM => self.m,
+            TP => self.p,
+            TS => self.s,
+            _ => 0,
+        }
+    }
+}
+
+// 三人麻雀のツモ和了の支払い方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SanmaTsumo {
+    #[default]
+    TsumoLoss, // ツモ損 (北家の分は誰も支払わない)
+    NorthBisection, // 北家折半 (北家の分を残りの2人で折半する)
+}
+
+// 三人麻雀のルール設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SanmaRules {
+    pub tsumo: SanmaTsumo, // ツモ和了の支払い方式
+    pub nuki_fan: usize,   // 抜きドラの北1枚ごとの飜
+}
+
+impl Default for SanmaRules {
+    fn default() -> Self {
+        Self {
+            tsumo: SanmaTsumo::default(),
+            nuki_fan: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ruleset_from_toml_works_01() {
+        let rules = Ruleset::from_toml(
+            r#"
+            disabled_yaku = [54]
+            kuitan = false
+            double_yakuman = false
+
+            [yaku_fan.59]
+            close = 13
+            open = 13
+
+            [red_fives]
+            m = 0
+
+            [points]
+            kiriage_mangan = true
+
+            [sanma]
+            tsumo = "north_bisection"
+            "#,
+        )
+        .unwrap();
+        assert!(!rules.is_yaku_enabled(54));
+        assert!(rules.is_yaku_enabled(55));
+        assert!(!rules.kuitan);
+        assert_eq!(
+            rules.yaku_fan[&59],
+            YakuFan {
+                close: 13,
+                open: 13
+            }
+        );
+        assert_eq!(
+            rules.red_fives,
+            RedFives {
+                m: 0,
+                p: TILE,
+                s: TILE
+            }
+        );
+        assert!(rules.points.kiriage_mangan);
+        assert!(rules.points.kazoe_yakuman);
+        assert_eq!(rules.sanma.unwrap().tsumo, SanmaTsumo::NorthBisection);
+        assert_eq!(rules.sanma.unwrap().nuki_fan, 1);
+    }
+
+    #[test]
+    fn ruleset_from_json_works_01() {
+        let rules = Ruleset::tenhou_sanma();
+        let json = serde_json::to_string(&rules).unwrap();
+        assert_eq!(Ruleset::from_json(&json).unwrap(), rules);
+        assert!(matches!(
+            Ruleset::from_json("{\"kuitan\": 1}"),
+            Err(MahjongError::InvalidRuleset(_))
+        ));
+    }
+
+    #[test]
+    fn ruleset_fan_of_works_01() {
+        // 四暗刻単騎はダブル役満なしの場合は役満
+        let tanki = yaku_by_id(48).unwrap();
+        assert_eq!(Ruleset::default().fan_of(tanki), (14, 0));
+        assert_eq!(Ruleset::tenhou().fan_of(tanki), (13, 0));
+        assert!(!Ruleset::tenhou().is_yaku_enabled(55));
+    }
+
+    #[test]
+    fn scoring_mode_from_toml_works_01() {
+        let mode: ScoringMode = toml::from_str("mode = \"mcr\"\nmin_fan = 0").unwrap();
+        assert_eq!(
+            mode,
+            ScoringMode::Mcr(McrRules {
+                min_fan: 0,
+                ..McrRules::default()
+            })
+        );
+        let mode: ScoringMode = toml::from_str("mode = \"riichi\"\nkuitan = false").unwrap();
+        assert!(matches!(mode, ScoringMode::Riichi(r) if !r.kuitan));
+    }
+}
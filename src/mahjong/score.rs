@@ -0,0 +1,713 @@
+use super::*;
+
+#[derive(Debug)]
+pub struct ScoreResult {
+    pub yaku: Vec<&'static Yaku>,  // 役一覧 (ドラは含まない)
+    pub fan: usize,                // 飜数 (ドラを含む, 役満の場合は0)
+    pub fu: usize,                 // 符
+    pub fu_breakdown: FuBreakdown, // 符の内訳
+    pub yakuman_count: usize,      // 役満倍数 (役満ではない場合は0)
+    pub dora: DoraInfo,            // ドラの内訳
+    pub almighty: Vec<Tile>,       // オールマイティの置き換え先
+    pub title: String,             // 満貫, 跳満など
+    pub is_dealer: bool,           // 親の和了
+    pub points: Points,            // (ロンの支払い, ツモ・子の支払い, ツモ・親の支払い)
+}
+
+// 点数計算の方式ごとの結果
+#[derive(Debug)]
+pub enum ScoreOutcome {
+    Riichi(ScoreResult),
+    Mcr(McrResult),
+    HongKong(HkResult),
+    Taiwanese(TwResult),
+}
+
+// 点数計算の方式を指定して計算する
+pub fn score_with_mode(
+    ctx: &HandContext,
+    mode: &ScoringMode,
+) -> Result<ScoreOutcome, MahjongError> {
+    match mode {
+        ScoringMode::Riichi(rules) => score(ctx, rules).map(ScoreOutcome::Riichi),
+        ScoringMode::Mcr(rules) => score_mcr(ctx, rules).map(ScoreOutcome::Mcr),
+        ScoringMode::HongKong(rules) => score_hk(ctx, rules).map(ScoreOutcome::HongKong),
+        ScoringMode::Taiwanese(rules) => score_tw(ctx, rules).map(ScoreOutcome::Taiwanese),
+    }
+}
+
+// 和了形の解釈のうち最も高い点数になるものを返却
+pub fn score(ctx: &HandContext, rules: &Ruleset) -> Result<ScoreResult, MahjongError> {
+    ctx.check_mentsu(4)?;
+    ctx.check_tiles()?;
+    rules.check_tiles(ctx)?;
+
+    let is_dealer = ctx.jikaze == WEA;
+    if ctx.yaku_flags.nagashi_mangan {
+        return score_nagashi_mangan(rules, is_dealer);
+    }
+
+    let yaku_ctxs = detect_winning(ctx);
+    if yaku_ctxs.is_empty() {
+        return Err(MahjongError::NotWinning);
+    }
+
+    let dora = calc_dora(ctx, rules);
+    let min_fan = rules.min_fan(ctx.honba);
+
+    let mut best: Option<ScoreResult> = None;
+    let mut insufficient_fan: Option<usize> = None; // 縛りを満たさない解釈の役の飜の最大
+    let mut kuitan_only = false; // 喰いタンなしで除いた断幺九のみが役の解釈がある
+    for yaku_ctx in &yaku_ctxs {
+        let YakuResult {
+            yaku,
+            fan: yaku_fan,
+            yakuman_count,
+            kuitan_dropped,
+        } = yaku_ctx.calc_yaku(rules);
+        if yaku.is_empty() {
+            kuitan_only |= kuitan_dropped;
+            continue;
+        }
+        if yakuman_count == 0 && yaku_fan < min_fan {
+            insufficient_fan = insufficient_fan.max(Some(yaku_fan));
+            continue;
+        }
+
+        let fu_breakdown = yaku_ctx.calc_fu_breakdown(&rules.fu);
+        let fu = fu_breakdown.total;
+        let fan = if yakuman_count > 0 {
+            0
+        } else {
+            yaku_fan + dora.dora_fan_sum
+        };
+        // 人和 (役満ではない場合) は他の役と複合せず, 他の役とドラの飜が上回る場合のみそちらを採用
+        let (yaku, fan) = match yaku.iter().find(|y| y.id == YAKU_RENHOU) {
+            Some(&renhou) if yakuman_count == 0 => {
+                let renhou_fan = rules.fan_of(renhou).0;
+                let other_fan = fan - renhou_fan;
+                if yaku.len() > 1 && other_fan > renhou_fan {
+                    let others = yaku.into_iter().filter(|y| y.id != YAKU_RENHOU);
+                    (others.collect(), other_fan)
+                } else {
+                    (vec![renhou], renhou_fan)
+                }
+            }
+            _ => (yaku, fan),
+        };
+        let points = match &rules.sanma {
+            Some(sanma) => get_points_sanma(
+                is_dealer,
+                fu,
+                fan,
+                yakuman_count,
+                &rules.points,
+                sanma.tsumo,
+            )?,
+            None => get_points(is_dealer, fu, fan, yakuman_count, &rules.points)?,
+        };
+
+        // 支払いが同じ場合は飜数,符の多い方を採用
+        if let Some(b) = &best {
+            if (points.0, fan, fu) <= (b.points.0, b.fan, b.fu) {
+                continue;
+            }
+        }
+        best = Some(ScoreResult {
+            yaku,
+            fan,
+            fu,
+            fu_breakdown,
+            yakuman_count,
+            dora: dora.clone(),
+            almighty: yaku_ctx.almighty_substitutions().to_vec(),
+            title: get_score_title(fu, fan, yakuman_count, &rules.points)?,
+            is_dealer,
+            points,
+        });
+    }
+
+    let Some(best) = best else {
+        if let Some(fan) = insufficient_fan {
+            return Err(MahjongError::InsufficientFan(min_fan, fan));
+        }
+        // 鳴いた断幺九があれば縛りを満たした場合は喰いタンなしによる不成立とする
+        let tanyao_fan = rules.fan_of(yaku_by_id(YAKU_TANYAO).unwrap()).1;
+        if kuitan_only && tanyao_fan >= min_fan {
+            return Err(MahjongError::KuitanNotAllowed);
+        }
+        if dora.dora_fan_sum > 0 {
+            return Err(MahjongError::DoraOnly(dora.dora_fan_sum));
+        }
+        return Err(MahjongError::NoYaku);
+    };
+
+    // 後付けなしの場合, 待ち牌のすべてで役がある必要がある
+    if !rules.atozuke {
+        for wait in find_waits(ctx, rules).waits {
+            if wait.yaku_nashi {
+                return Err(MahjongError::Atozuke(wait.tile));
+            }
+        }
+    }
+
+    Ok(best)
+}
+
+// 流し満貫は和了形・ドラによらず, 符は30符として扱う
+fn score_nagashi_mangan(rules: &Ruleset, is_dealer: bool) -> Result<ScoreResult, MahjongError> {
+    let yaku = yaku_by_id(YAKU_NAGASHI_MANGAN).unwrap();
+    if !rules.is_yaku_enabled(yaku.id) {
+        return Err(MahjongError::NoYaku);
+    }
+    let (fan_close, _) = rules.fan_of(yaku);
+    let (fan, yakuman_count) = if fan_close >= 13 {
+        (0, fan_close - 12)
+    } else {
+        (fan_close, 0)
+    };
+    let fu = 30;
+    let points = match &rules.sanma {
+        Some(sanma) => get_points_sanma(
+            is_dealer,
+            fu,
+            fan,
+            yakuman_count,
+            &rules.points,
+            sanma.tsumo,
+        )?,
+        None => get_points(is_dealer, fu, fan, yakuman_count, &rules.points)?,
+    };
+    Ok(ScoreResult {
+        yaku: vec![yaku],
+        fan,
+        fu,
+        fu_breakdown: FuBreakdown::new(vec![]),
+        yakuman_count,
+        dora: DoraInfo::new(0, 0, 0, 0, 0),
+        almighty: vec![],
+        title: get_score_title(fu, fan, yakuman_count, &rules.points)?,
+        is_dealer,
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiles(v: &[(Type, Tnum)]) -> Vec<TileWithDora> {
+        v.iter()
+            .map(|&(t, n)| TileWithDora(Tile(t, n), 0))
+            .collect()
+    }
+
+    #[test]
+    fn score_works_01() {
+        // 平和のみ 30符1飜
+        let ctx = HandContext::new(
+            tiles(&[
+                (TM, 1),
+                (TM, 2),
+                (TM, 3),
+                (TM, 5),
+                (TM, 6),
+                (TM, 7),
+                (TP, 6),
+                (TP, 7),
+                (TP, 8),
+                (TS, 6),
+                (TS, 7),
+                (TZ, WWE),
+                (TZ, WWE),
+            ]),
+            vec![],
+            TileWithDora(Tile(TS, 8), 0),
+            false,
+            WEA,
+            WNO,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            YakuFlags::default(),
+        );
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.yaku.len(), 1);
+        assert_eq!((res.fan, res.fu, res.yakuman_count), (1, 30, 0));
+        assert_eq!(res.points, (1000, 300, 500));
+    }
+
+    #[test]
+    fn score_works_02() {
+        // 一色三順なしの場合は 平和+一盃口 より 三暗刻+三連刻 の方が高い
+        let ctx = HandContext::new(
+            tiles(&[
+                (TM, 1),
+                (TM, 2),
+                (TM, 3),
+                (TM, 1),
+                (TM, 2),
+                (TM, 3),
+                (TM, 1),
+                (TM, 2),
+                (TM, 3),
+                (TS, 6),
+                (TS, 7),
+                (TZ, WWE),
+                (TZ, WWE),
+            ]),
+            vec![],
+            TileWithDora(Tile(TS, 8), 0),
+            false,
+            WEA,
+            WNO,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            YakuFlags::default(),
+        );
+        let mut rules = Ruleset::default();
+        rules.disabled_yaku.insert(54);
+        let res = score(&ctx, &rules).unwrap();
+        assert_eq!((res.fan, res.fu), (4, 50));
+        assert_eq!(res.points.0, 8000);
+        assert_eq!(res.fu_breakdown.subtotal, 46);
+        assert_eq!(
+            res.fu_breakdown.to_string(),
+            "副底: 20符\n門前加符: 10符\n暗刻 m3: 4符\n暗刻 m2: 4符\n暗刻 m1: 8符\n合計: 46符 → 50符"
+        );
+    }
+
+    #[test]
+    fn score_works_03() {
+        // 役なし
+        let ctx = HandContext::new(
+            tiles(&[
+                (TM, 1),
+                (TM, 2),
+                (TM, 3),
+                (TM, 5),
+                (TM, 6),
+                (TM, 7),
+                (TP, 6),
+                (TP, 7),
+                (TP, 8),
+                (TS, 6),
+                (TS, 8),
+                (TZ, WWE),
+                (TZ, WWE),
+            ]),
+            vec![],
+            TileWithDora(Tile(TS, 7), 0),
+            false,
+            WEA,
+            WNO,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            YakuFlags::default(),
+        );
+        assert_eq!(
+            score(&ctx, &Ruleset::default()).unwrap_err(),
+            MahjongError::NoYaku
+        );
+    }
+
+    #[test]
+    fn score_works_04() {
+        // 和了形ではない
+        let ctx = HandContext::new(
+            tiles(&[
+                (TM, 1),
+                (TM, 2),
+                (TM, 3),
+                (TM, 5),
+                (TM, 6),
+                (TM, 7),
+                (TP, 6),
+                (TP, 7),
+                (TP, 8),
+                (TS, 6),
+                (TS, 8),
+                (TZ, WWE),
+                (TZ, WWE),
+            ]),
+            vec![],
+            TileWithDora(Tile(TS, 9), 0),
+            false,
+            WEA,
+            WNO,
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+            YakuFlags::default(),
+        );
+        assert_eq!(
+            score(&ctx, &Ruleset::default()).unwrap_err(),
+            MahjongError::NotWinning
+        );
+    }
+
+    fn riichi_tsumo_ctx(yaku_flags: YakuFlags) -> HandContext {
+        // 東場の東家, 嵌張ツモ, 222m暗刻, 東の雀頭
+        HandContext {
+            yaku_flags: YakuFlags {
+                menzentsumo: true,
+                riichi: true,
+                ..yaku_flags
+            },
+            ..test_ctx("222m456p13s789s11z+2s", true, WEA)
+        }
+    }
+
+    #[test]
+    fn score_works_05() {
+        // 連風牌の雀頭の符
+        let ctx = riichi_tsumo_ctx(YakuFlags::default());
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!((res.fu_breakdown.subtotal, res.fu), (30, 30));
+
+        let mut rules = Ruleset::default();
+        rules.fu.double_wind_pair_fu = 4;
+        let res = score(&ctx, &rules).unwrap();
+        assert_eq!((res.fu_breakdown.subtotal, res.fu), (32, 40));
+    }
+
+    #[test]
+    fn score_works_06() {
+        // 嶺上開花のツモ符
+        let ctx = riichi_tsumo_ctx(YakuFlags {
+            rinshankaihou: true,
+            ..YakuFlags::default()
+        });
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.fu_breakdown.subtotal, 30);
+
+        let mut rules = Ruleset::default();
+        rules.fu.rinshan_tsumo_fu = false;
+        let res = score(&ctx, &rules).unwrap();
+        assert_eq!(res.fu_breakdown.subtotal, 28);
+    }
+
+    #[test]
+    fn score_works_07() {
+        // 花牌: 1枚1飜, 自風の花牌+1飜, 春夏秋冬揃いで+2飜
+        let mut ctx = riichi_tsumo_ctx(YakuFlags::default());
+        ctx.flowers = vec![
+            Tile(TH, FSP),
+            Tile(TH, FSU),
+            Tile(TH, FAU),
+            Tile(TH, FWI),
+            Tile(TH, FOR),
+        ];
+        let rules = Ruleset {
+            flower: FlowerRules {
+                fan_per_flower: 1,
+                own_seat_fan: 1,
+                full_set_fan: 2,
+            },
+            ..Ruleset::default()
+        };
+        let res = score(&ctx, &rules).unwrap();
+        assert_eq!(res.dora.flower_fan, 8);
+        assert_eq!(res.fan, 10);
+    }
+
+    #[test]
+    fn score_works_08() {
+        // 三人麻雀: 北の抜きドラ, 萬子の2~8は使えない
+        let mut ctx = riichi_tsumo_ctx(YakuFlags::default());
+        ctx.nuki_dora = vec![TileWithDora(Tile(TZ, WNO), 0); 2];
+        let rules = Ruleset {
+            sanma: Some(SanmaRules::default()),
+            ..Ruleset::default()
+        };
+        assert_eq!(
+            score(&ctx, &rules).unwrap_err(),
+            MahjongError::UnavailableTile(Tile(TM, 2))
+        );
+
+        let hand = parse_hand("999m456p13s789s11z+2s").unwrap();
+        ctx.hand_tiles = hand.hand_tiles;
+        let res = score(&ctx, &rules).unwrap();
+        assert_eq!(res.dora.nuki_dora_fan, 2);
+        assert_eq!(res.fan, 4);
+    }
+
+    #[test]
+    fn score_works_09() {
+        // ドラ表示牌 1m → ドラ 2m (暗刻で3枚), 裏ドラ表示牌 北 → ドラ 東 (雀頭で2枚)
+        let mut ctx = riichi_tsumo_ctx(YakuFlags::default());
+        ctx.set_dora_indicators(&[Tile(TM, 1)], &[Tile(TZ, WNO)], &Ruleset::default());
+        assert_eq!(ctx.dora, vec![Tile(TM, 2)]);
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!((res.dora.dora_fan, res.dora.ura_dora_fan), (3, 2));
+    }
+
+    #[test]
+    fn score_works_10() {
+        // 人和: 既定は満貫, 設定で役満
+        let mut ctx = riichi_tsumo_ctx(YakuFlags::default());
+        ctx.tsumo = false;
+        ctx.jikaze = WSO;
+        ctx.yaku_flags = YakuFlags {
+            renhou: true,
+            ..YakuFlags::default()
+        };
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.fan, 5);
+        assert_eq!(res.points.0, 8000);
+
+        let mut rules = Ruleset::default();
+        rules.yaku_fan.insert(
+            YAKU_RENHOU,
+            YakuFan {
+                close: 13,
+                open: 13,
+            },
+        );
+        let res = score(&ctx, &rules).unwrap();
+        assert_eq!((res.fan, res.yakuman_count), (0, 1));
+        assert_eq!(res.points.0, 32000);
+    }
+
+    #[test]
+    fn score_works_11() {
+        // オープン立直: リーチしていない他家の放銃は役満
+        let mut ctx = riichi_tsumo_ctx(YakuFlags {
+            open_riichi: true,
+            ..YakuFlags::default()
+        });
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.fan, 3);
+
+        ctx.tsumo = false;
+        ctx.yaku_flags.menzentsumo = false;
+        ctx.yaku_flags.open_riichi_non_riichi_loser = true;
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.yakuman_count, 1);
+        assert_eq!(res.yaku[0].name, "オープン立直放銃");
+    }
+
+    #[test]
+    fn score_works_12() {
+        // 流し満貫: 和了形によらず満貫
+        let mut ctx = riichi_tsumo_ctx(YakuFlags::default());
+        ctx.agari_tile = TileWithDora(Tile(TZ, DRE), 0);
+        ctx.yaku_flags = YakuFlags {
+            nagashi_mangan: true,
+            ..YakuFlags::default()
+        };
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.points, (12000, 4000, 0));
+        assert_eq!(res.title, "満貫");
+    }
+
+    #[test]
+    fn score_works_13() {
+        // 喰いタンなし, 赤五の枚数の上限
+        let ctx = test_ctx("234m067p3s678s+3s (chi 234p)", false, WSO);
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.fan, 2);
+
+        let rules = Ruleset {
+            kuitan: false,
+            ..Ruleset::default()
+        };
+        assert_eq!(
+            score(&ctx, &rules).unwrap_err(),
+            MahjongError::KuitanNotAllowed
+        );
+
+        // 喰いタンでも2飜縛りを満たさない場合は役なしとする
+        let mut ctx = ctx;
+        ctx.honba = 5;
+        let rules = Ruleset {
+            shibari: Some(ShibariRules::default()),
+            ..rules
+        };
+        assert_eq!(score(&ctx, &rules).unwrap_err(), MahjongError::DoraOnly(1));
+        let rules = Ruleset {
+            kuitan: true,
+            ..rules
+        };
+        assert_eq!(
+            score(&ctx, &rules).unwrap_err(),
+            MahjongError::InsufficientFan(2, 1)
+        );
+        ctx.honba = 0;
+        assert_eq!(
+            score(&ctx, &Ruleset::competition()).unwrap_err(),
+            MahjongError::TooManyRedFives(Tile(TP, 5))
+        );
+    }
+
+    #[test]
+    fn score_works_14() {
+        // 後付けなし: 中のポンがあれば 25m 待ちのどちらでも役がある
+        let mut ctx = test_ctx("34m456p789s11z+2m (pon 777z)", false, WSO);
+        assert!(score(&ctx, &Ruleset::default()).is_ok());
+        let rules = Ruleset {
+            atozuke: false,
+            ..Ruleset::default()
+        };
+        assert!(score(&ctx, &rules).is_ok());
+
+        // 14m 待ちのうち 1m では役がないため, 4m の喰いタンは後付けになる
+        let hand = parse_hand("23m456p678s55s+4m (chi 456s)").unwrap();
+        ctx.hand_tiles = hand.hand_tiles;
+        ctx.fuuro = hand.fuuro;
+        ctx.agari_tile = hand.agari_tile.unwrap();
+        assert_eq!(
+            score(&ctx, &rules).unwrap_err(),
+            MahjongError::Atozuke(Tile(TM, 1))
+        );
+        let rules = Ruleset {
+            kuitan: false,
+            ..Ruleset::default()
+        };
+        assert_eq!(
+            score(&ctx, &rules).unwrap_err(),
+            MahjongError::KuitanNotAllowed
+        );
+    }
+
+    #[test]
+    fn score_works_15() {
+        // 5本場から2飜縛り: 平和のみ (ドラ1) は不成立
+        let mut ctx = HandContext {
+            dora: vec![Tile(TM, 1)],
+            ..test_ctx("123m567m678p67s33z+8s", false, WSO)
+        };
+        let rules = Ruleset {
+            shibari: Some(ShibariRules::default()),
+            ..Ruleset::default()
+        };
+        ctx.honba = 4;
+        assert_eq!(score(&ctx, &rules).unwrap().fan, 2);
+        ctx.honba = 5;
+        assert_eq!(
+            score(&ctx, &rules).unwrap_err(),
+            MahjongError::InsufficientFan(2, 1)
+        );
+    }
+
+    #[test]
+    fn score_works_16() {
+        // 役なし: ドラのみの場合は区別する
+        let mut ctx = test_ctx("234m067p3s678s+3s (chi 123p)", false, WSO);
+        let err = score(&ctx, &Ruleset::default()).unwrap_err();
+        assert_eq!(err, MahjongError::DoraOnly(1));
+        assert!(err.is_yaku_nashi());
+
+        ctx.hand_tiles = parse_tiles("234m567p3s678s").unwrap();
+        assert_eq!(
+            score(&ctx, &Ruleset::default()).unwrap_err(),
+            MahjongError::NoYaku
+        );
+    }
+
+    #[test]
+    fn score_works_17() {
+        // 双碰のロンで完成した刻子は明刻 (四暗刻ではなく三暗刻+対々和)
+        let mut ctx = test_ctx("111m333p55s777s99s+5s", false, WSO);
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.yakuman_count, 0);
+        let names: Vec<&str> = res.yaku.iter().map(|y| y.name).collect();
+        assert!(names.contains(&"三暗刻") && names.contains(&"対々和"));
+        assert_eq!(res.fu_breakdown.subtotal, 48);
+        assert!(res
+            .fu_breakdown
+            .items
+            .contains(&(FuItem::Block(BlockType::Pon, Tile(TS, 5)), 2)));
+
+        ctx.tsumo = true;
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        assert_eq!(res.yaku[0].name, "四暗刻");
+    }
+
+    #[test]
+    fn score_works_18() {
+        // 34456m に 5m: 345m の両面 (平和) と 456m の嵌張のうち高い方を採用
+        let ctx = test_ctx("34456m234p678s88s+5m", false, WSO);
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        let names: Vec<&str> = res.yaku.iter().map(|y| y.name).collect();
+        assert_eq!(names, vec!["断幺九", "平和"]);
+        assert_eq!((res.fan, res.fu), (2, 30));
+        assert_eq!(res.points.0, 2000);
+    }
+
+    #[test]
+    fn score_works_19() {
+        // 16枚 (5面子) の和了形は台湾麻雀以外の採点モードでは扱わない
+        let mut ctx = test_ctx("123m456m789m123p456p1z+1z", false, WSO);
+        ctx.mentsu = 5;
+        for mode in [
+            ScoringMode::Riichi(Ruleset::default()),
+            ScoringMode::Mcr(McrRules::default()),
+            ScoringMode::HongKong(HkRules::default()),
+        ] {
+            assert_eq!(
+                score_with_mode(&ctx, &mode).unwrap_err(),
+                MahjongError::InvalidMentsuCount(5)
+            );
+        }
+        assert!(score_with_mode(&ctx, &ScoringMode::Taiwanese(TwRules::default())).is_ok());
+    }
+
+    #[test]
+    fn score_works_20() {
+        // 喰いピンフ形の30符と七対子の25符も内訳の合計と一致する
+        let mut ctx = test_ctx("34m567p66s+5m (chi 345s) (chi 678s)", false, WSO);
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        let fu = &res.fu_breakdown;
+        assert!(fu.items.contains(&(FuItem::OpenPinfu, 10)));
+        assert_eq!(
+            fu.items.iter().map(|(_, fu)| fu).sum::<usize>(),
+            fu.subtotal
+        );
+        assert_eq!((fu.subtotal, fu.total, res.fu), (30, 30, 30));
+
+        let hand = parse_hand("1122m3344p5566s7z+7z").unwrap();
+        ctx.hand_tiles = hand.hand_tiles;
+        ctx.fuuro = hand.fuuro;
+        ctx.agari_tile = hand.agari_tile.unwrap();
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        let fu = &res.fu_breakdown;
+        assert_eq!(
+            fu.items.iter().map(|(_, fu)| fu).sum::<usize>(),
+            fu.subtotal
+        );
+        assert_eq!((fu.subtotal, fu.total, res.fu), (25, 25, 25));
+    }
+
+    #[test]
+    fn score_works_21() {
+        // 人和は他の役と複合せず満貫, 他の役の方が高い場合は人和を数えない
+        let mut ctx = HandContext {
+            dora: vec![Tile(TS, 7)],
+            yaku_flags: YakuFlags {
+                renhou: true,
+                ..YakuFlags::default()
+            },
+            ..test_ctx("23m456p567s678s88s+4m", false, WSO)
+        };
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        let names: Vec<&str> = res.yaku.iter().map(|y| y.name).collect();
+        assert_eq!(names, vec!["人和"]);
+        assert_eq!((res.fan, res.points.0), (5, 8000));
+
+        let hand = parse_hand("123456789m1123m+4m").unwrap();
+        ctx.hand_tiles = hand.hand_tiles;
+        ctx.agari_tile = hand.agari_tile.unwrap();
+        ctx.dora = vec![];
+        let res = score(&ctx, &Ruleset::default()).unwrap();
+        let names: Vec<&str> = res.yaku.iter().map(|y| y.name).collect();
+        assert!(!names.contains(&"人和"));
+        assert_eq!((res.fan, res.points.0), (9, 16000));
+    }
+}
@@ -0,0 +1,232 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShantenInfo {
+    pub normal: Shanten,             // 4面子1雀頭
+    pub seven_pair: Option<Shanten>, // 七対子 (鳴きがある場合は None)
+    pub kokushi: Option<Shanten>,    // 国士無双 (鳴きがある場合は None)
+    pub min: Shanten,                // 上記の最小値
+}
+
+// 手牌 (鳴いた牌は含まない) と副露の数から向聴数を計算
+pub fn shanten(hand: &TileTable, meld_count: usize) -> ShantenInfo {
+    let normal = calc_shanten_normal(hand, meld_count);
+    let (seven_pair, kokushi) = if meld_count == 0 {
+        (
+            Some(calc_shanten_seven_pair(hand)),
+            Some(calc_shanten_kokushi(hand)),
+        )
+    } else {
+        (None, None)
+    };
+
+    let mut min = normal;
+    for s in [seven_pair, kokushi].into_iter().flatten() {
+        min = min.min(s);
+    }
+
+    ShantenInfo {
+        normal,
+        seven_pair,
+        kokushi,
+        min,
+    }
+}
+
+// 面子,塔子,雀頭の数から向聴数を求める (8 - 2*面子 - 塔子 - 雀頭)
+pub fn calc_shanten_normal(hand: &TileTable, meld_count: usize) -> Shanten {
+    let mut hand = *hand;
+    let mut best = 8;
+    search_blocks(&mut hand, (TM, 1), meld_count, 0, 0, &mut best);
+    best
+}
+
+pub fn calc_shanten_seven_pair(hand: &TileTable) -> Shanten {
+    let mut pairs = 0;
+    let mut kinds = 0;
+    for (i, j) in tile_kinds() {
+        if hand[i][j] >= 2 {
+            pairs += 1;
+        }
+        if hand[i][j] >= 1 {
+            kinds += 1;
+        }
+    }
+    // 同じ牌4枚は2対子とみなさないため, 種類数が足りない分だけ向聴数が増える
+    6 - pairs + (7 - kinds).max(0)
+}
+
+pub fn calc_shanten_kokushi(hand: &TileTable) -> Shanten {
+    let mut kinds = 0;
+    let mut has_pair = false;
+    for (i, j) in tile_kinds() {
+        if Tile(i, j).is_end() && hand[i][j] >= 1 {
+            kinds += 1;
+            if hand[i][j] >= 2 {
+                has_pair = true;
+            }
+        }
+    }
+    13 - kinds - if has_pair { 1 } else { 0 }
+}
+
+// 手牌として使われる牌の種類 (萬子,筒子,索子の1~9と字牌の1~7)
+pub fn tile_kinds() -> impl Iterator<Item = (Type, Tnum)> {
+    (TM..=TZ).flat_map(|i| {
+        let max = if i == TZ { DRE } else { 9 };
+        (1..=max).map(move |j| (i, j))
+    })
+}
+
+fn next_kind((i, j): (Type, Tnum)) -> Option<(Type, Tnum)> {
+    let max = if i == TZ { DRE } else { 9 };
+    if j < max {
+        Some((i, j + 1))
+    } else if i < TZ {
+        Some((i + 1, 1))
+    } else {
+        None
+    }
+}
+
+// 面子, 塔子, 雀頭, 孤立牌への分け方を全探索して向聴数の最小値を best に記録
+// mentsu_detect の get_mentsu, get_koutsu は全ての牌が面子になる分け方しか返さない
+// (塔子や孤立牌が残ると None) ため, 和了形でない手牌には使えない
+fn search_blocks(
+    hand: &mut TileTable,
+    pos: (Type, Tnum),
+    mentsu: usize,
+    taatsu: usize,
+    pair: usize,
+    best: &mut Shanten,
+) {
+    // 牌が残っている位置まで進める
+    let mut pos = Some(pos);
+    while let Some((i, j)) = pos {
+        if hand[i][j] > 0 {
+            break;
+        }
+        pos = next_kind((i, j));
+    }
+
+    let (i, j) = match pos {
+        Some(p) => p,
+        None => {
+            // 面子+塔子は4つまでしか数えない
+            let taatsu = taatsu.min(4 - mentsu.min(4));
+            let s = 8 - 2 * mentsu as Shanten - taatsu as Shanten - pair as Shanten;
+            *best = (*best).min(s);
+            return;
+        }
+    };
+    let suited = i != TZ;
+
+    // 刻子
+    if hand[i][j] >= 3 {
+        hand[i][j] -= 3;
+        search_blocks(hand, (i, j), mentsu + 1, taatsu, pair, best);
+        hand[i][j] += 3;
+    }
+    // 順子
+    if suited && j <= 7 && hand[i][j + 1] > 0 && hand[i][j + 2] > 0 {
+        hand[i][j] -= 1;
+        hand[i][j + 1] -= 1;
+        hand[i][j + 2] -= 1;
+        search_blocks(hand, (i, j), mentsu + 1, taatsu, pair, best);
+        hand[i][j] += 1;
+        hand[i][j + 1] += 1;
+        hand[i][j + 2] += 1;
+    }
+    // 雀頭, 対子
+    if hand[i][j] >= 2 {
+        hand[i][j] -= 2;
+        if pair == 0 {
+            search_blocks(hand, (i, j), mentsu, taatsu, 1, best);
+        }
+        search_blocks(hand, (i, j), mentsu, taatsu + 1, pair, best);
+        hand[i][j] += 2;
+    }
+    // 両面, 辺張
+    if suited && j <= 8 && hand[i][j + 1] > 0 {
+        hand[i][j] -= 1;
+        hand[i][j + 1] -= 1;
+        search_blocks(hand, (i, j), mentsu, taatsu + 1, pair, best);
+        hand[i][j] += 1;
+        hand[i][j + 1] += 1;
+    }
+    // 嵌張
+    if suited && j <= 7 && hand[i][j + 2] > 0 {
+        hand[i][j] -= 1;
+        hand[i][j + 2] -= 1;
+        search_blocks(hand, (i, j), mentsu, taatsu + 1, pair, best);
+        hand[i][j] += 1;
+        hand[i][j + 2] += 1;
+    }
+    // 孤立牌
+    hand[i][j] -= 1;
+    search_blocks(hand, (i, j), mentsu, taatsu, pair, best);
+    hand[i][j] += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shanten_works_01() {
+        // 和了形
+        let info = shanten(&to_table("123m456p789s11122z"), 0);
+        assert_eq!(info.normal, -1);
+        assert_eq!(info.min, -1);
+    }
+
+    #[test]
+    fn shanten_works_02() {
+        // 両面待ちの聴牌
+        let info = shanten(&to_table("123m456p789s23s11z"), 0);
+        assert_eq!(info.normal, 0);
+        assert_eq!(info.min, 0);
+    }
+
+    #[test]
+    fn shanten_works_03() {
+        // 七対子の一向聴
+        let info = shanten(&to_table("1199m2288p337s14z"), 0);
+        assert_eq!(info.seven_pair, Some(1));
+        assert_eq!(info.min, 1);
+    }
+
+    #[test]
+    fn shanten_works_04() {
+        // 国士無双の聴牌
+        let info = shanten(&to_table("19m19p19s1234566z"), 0);
+        assert_eq!(info.kokushi, Some(0));
+        assert_eq!(info.min, 0);
+    }
+
+    #[test]
+    fn shanten_works_05() {
+        // 副露3つ, 延べ単待ち
+        let info = shanten(&to_table("2345m"), 3);
+        assert_eq!(info.normal, 0);
+        assert_eq!(info.seven_pair, None);
+        assert_eq!(info.kokushi, None);
+    }
+
+    #[test]
+    fn shanten_works_06() {
+        // 4枚使いは七対子の対子2つとはみなさない
+        let info = shanten(&to_table("1111m2288p3377s11z"), 0);
+        assert_eq!(info.seven_pair, Some(1));
+    }
+
+    #[test]
+    fn shanten_works_07() {
+        // 面子も塔子もない場合は八向聴
+        let info = shanten(&to_table("147m258p369s1234z"), 0);
+        assert_eq!(info.normal, 8);
+        // 塔子4つで四向聴
+        let info = shanten(&to_table("13m468p2357s1267z"), 0);
+        assert_eq!(info.normal, 4);
+    }
+}
@@ -66,29 +66,25 @@ impl Tile {
                 && (self.1 == 2 || self.1 == 3 || self.1 == 4 || self.1 == 6 || self.1 == 8))
     }
 
-    pub fn from_symbol(s: &str) -> Self {
-        let b = s.as_bytes();
-        let n = b[1] - b'0';
-        let t = match b[0] as char {
-            'm' => 0,
-            'p' => 1,
-            's' => 2,
-            'z' => 3,
-            'h' => 4,
-            _ => panic!("invalid Tile type"),
-        };
-        Self(t, n as usize)
+    // "m1" のような表記から変換
+    pub fn from_symbol(s: &str) -> Result<Self, MahjongError> {
+        let invalid = || MahjongError::InvalidTileSymbol(s.to_string());
+        let mut chars = s.chars();
+        let t = chars.next().and_then(type_from_char).ok_or_else(invalid)?;
+        let n = chars
+            .next()
+            .and_then(|c| c.to_digit(10))
+            .ok_or_else(invalid)? as usize;
+        if chars.next().is_some() || !is_valid_tnum(t, n) {
+            return Err(invalid());
+        }
+        Ok(Self(t, n))
     }
 }
 
 impl fmt::Display for Tile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}{}",
-            ['m', 'p', 's', 'z', 'h'][self.0 as usize],
-            self.1
-        )
+        write!(f, "{}{}", ['m', 'p', 's', 'z', 'h'][self.0], self.1)
     }
 }
 
@@ -100,17 +96,17 @@ impl fmt::Debug for Tile {
 
 impl PartialOrd for Tile {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        if self.0 != other.0 {
-            return Some(self.0.cmp(&other.0));
-        }
-
-        self.1.partial_cmp(&other.1)
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Tile {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        if self.0 != other.0 {
+            return self.0.cmp(&other.0);
+        }
+
+        self.1.cmp(&other.1)
     }
 }
 
@@ -118,19 +114,52 @@ impl Ord for Tile {
 pub struct TileWithDora(pub Tile, pub Dora); // (tile, dora bonus)
 
 impl TileWithDora {
-    pub fn from_symbol(s: &str) -> Self {
-        let b = s.as_bytes();
-        let n = b[1] - b'0';
-        let d = if b.len() < 2 { 0 } else { b[2] - b'0' };
-        let t = match b[0] as char {
-            'm' => 0,
-            'p' => 1,
-            's' => 2,
-            'z' => 3,
-            'h' => 4,
-            _ => panic!("invalid Tile type"),
+    // "p5", "p51" のような表記から変換 (3文字目以降はドラの飜数)
+    pub fn from_symbol(s: &str) -> Result<Self, MahjongError> {
+        let invalid = || MahjongError::InvalidTileSymbol(s.to_string());
+        let (tile, dora) = match (s.get(..2), s.get(2..)) {
+            (Some(t), Some(d)) => (t, d),
+            _ => return Err(invalid()),
+        };
+        let tile = Tile::from_symbol(tile).map_err(|_| invalid())?;
+        let dora = if dora.is_empty() {
+            0
+        } else {
+            dora.parse::<Dora>().map_err(|_| invalid())?
         };
-        Self(Tile(t, n as usize), d as usize)
+        Ok(Self(tile, dora))
+    }
+}
+
+impl std::str::FromStr for Tile {
+    type Err = MahjongError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_symbol(s)
+    }
+}
+
+impl TryFrom<&str> for Tile {
+    type Error = MahjongError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_symbol(s)
+    }
+}
+
+impl std::str::FromStr for TileWithDora {
+    type Err = MahjongError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_symbol(s)
+    }
+}
+
+impl TryFrom<&str> for TileWithDora {
+    type Error = MahjongError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_symbol(s)
     }
 }
 
@@ -139,7 +168,7 @@ impl fmt::Display for TileWithDora {
         write!(
             f,
             "{}{}{}",
-            ['m', 'p', 's', 'z', 'h'][self.0 .0 as usize],
+            ['m', 'p', 's', 'z', 'h'][self.0 .0],
             self.0 .1,
             self.1
         )
@@ -154,20 +183,93 @@ impl fmt::Debug for TileWithDora {
 
 impl PartialOrd for TileWithDora {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TileWithDora {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         if self.0 != other.0 {
-            return Some(self.0.cmp(&other.0));
+            return self.0.cmp(&other.0);
         }
 
-        self.1.partial_cmp(&other.1)
+        self.1.cmp(&other.1)
     }
 }
 
-impl Ord for TileWithDora {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+// 種別,数字の組み合わせとして存在する牌か
+fn is_valid_tnum(t: Type, n: Tnum) -> bool {
+    matches!((t, n), (TM | TP | TS, 1..=9) | (TZ | TH, 1..=8))
+}
+
+fn type_from_char(c: char) -> Option<Type> {
+    match c {
+        'm' => Some(TM),
+        'p' => Some(TP),
+        's' => Some(TS),
+        'z' => Some(TZ),
+        'h' => Some(TH),
+        _ => None,
     }
 }
 
+// "123m406p11z" のような MPSZ 形式の文字列を牌の列に変換 (0 は赤5としてドラ1を付与)
+pub fn parse_tiles(s: &str) -> Result<Vec<TileWithDora>, MahjongError> {
+    let invalid = || MahjongError::InvalidTileSymbol(s.to_string());
+    let mut output = Vec::new();
+    let mut nums: Vec<usize> = Vec::new();
+    for c in s.chars() {
+        if let Some(n) = c.to_digit(10) {
+            nums.push(n as usize);
+            continue;
+        }
+        let t = type_from_char(c).ok_or_else(invalid)?;
+        if nums.is_empty() {
+            return Err(invalid());
+        }
+        for &n in &nums {
+            let tile = if n == 0 && t <= TS {
+                TileWithDora(Tile(t, 5), 1)
+            } else if is_valid_tnum(t, n) {
+                TileWithDora(Tile(t, n), 0)
+            } else {
+                return Err(invalid());
+            };
+            output.push(tile);
+        }
+        nums.clear();
+    }
+    if !nums.is_empty() {
+        return Err(invalid());
+    }
+    Ok(output)
+}
+
+// 牌の列を MPSZ 形式の文字列に変換 (並び順は保持し,同じ種別が続く部分をまとめる)
+// ドラ1の5は 0 で表すが,それ以外のドラ情報は表現できないため失われる
+pub fn format_tiles(tiles: &[TileWithDora]) -> String {
+    let mut output = String::new();
+    let mut prev: Option<Type> = None;
+    for TileWithDora(Tile(t, n), d) in tiles {
+        if let Some(p) = prev {
+            if p != *t {
+                output.push(['m', 'p', 's', 'z', 'h'][p]);
+            }
+        }
+        let n = if *n == 5 && *d == 1 && *t <= TS {
+            0
+        } else {
+            *n
+        };
+        output.push_str(&n.to_string());
+        prev = Some(*t);
+    }
+    if let Some(p) = prev {
+        output.push(['m', 'p', 's', 'z', 'h'][p]);
+    }
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +405,76 @@ mod tests {
         let tile = Tile(TM, 8);
         assert!(!tile.is_terminal());
     }
+
+    #[test]
+    fn parse_tiles_works_01() {
+        let tiles = parse_tiles("123m406p").unwrap();
+        assert_eq!(tiles.len(), 6);
+        assert_eq!(tiles[0], TileWithDora(Tile(TM, 1), 0));
+        assert_eq!(tiles[4], TileWithDora(Tile(TP, 5), 1));
+    }
+
+    #[test]
+    fn parse_tiles_works_02() {
+        assert_eq!(
+            parse_tiles("19z"),
+            Err(MahjongError::InvalidTileSymbol("19z".to_string()))
+        );
+        assert_eq!(
+            parse_tiles("123"),
+            Err(MahjongError::InvalidTileSymbol("123".to_string()))
+        );
+        assert_eq!(
+            parse_tiles("12x"),
+            Err(MahjongError::InvalidTileSymbol("12x".to_string()))
+        );
+    }
+
+    #[test]
+    fn format_tiles_works_01() {
+        let s = "123m406p789s11z";
+        assert_eq!(format_tiles(&parse_tiles(s).unwrap()), s);
+    }
+
+    #[test]
+    fn from_symbol_works_01() {
+        assert_eq!(Tile::from_symbol("m1"), Ok(Tile(TM, 1)));
+        assert_eq!("z7".parse::<Tile>(), Ok(Tile(TZ, DRE)));
+        assert_eq!(
+            TileWithDora::from_symbol("p51"),
+            Ok(TileWithDora(Tile(TP, 5), 1))
+        );
+        assert_eq!(
+            TileWithDora::try_from("p5"),
+            Ok(TileWithDora(Tile(TP, 5), 0))
+        );
+    }
+
+    #[test]
+    fn from_symbol_works_02() {
+        assert_eq!(
+            Tile::from_symbol("x1"),
+            Err(MahjongError::InvalidTileSymbol("x1".to_string()))
+        );
+        assert_eq!(
+            Tile::from_symbol("m"),
+            Err(MahjongError::InvalidTileSymbol("m".to_string()))
+        );
+        assert_eq!(
+            Tile::from_symbol("z9"),
+            Err(MahjongError::InvalidTileSymbol("z9".to_string()))
+        );
+        assert_eq!(
+            Tile::from_symbol("m12"),
+            Err(MahjongError::InvalidTileSymbol("m12".to_string()))
+        );
+        assert_eq!(
+            TileWithDora::from_symbol("p"),
+            Err(MahjongError::InvalidTileSymbol("p".to_string()))
+        );
+        assert_eq!(
+            TileWithDora::from_symbol("p5x"),
+            Err(MahjongError::InvalidTileSymbol("p5x".to_string()))
+        );
+    }
 }
@@ -0,0 +1,388 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+// 台湾麻雀の台ID
+pub const TW_CONCEALED_HAND: usize = 1;
+pub const TW_SELF_DRAWN: usize = 2;
+pub const TW_CONCEALED_SELF_DRAWN: usize = 3;
+pub const TW_SEAT_FLOWER: usize = 4;
+pub const TW_FLOWER_SET: usize = 5;
+pub const TW_ALL_FLOWERS: usize = 6;
+pub const TW_DRAGON_PUNG: usize = 7;
+pub const TW_PREVALENT_WIND: usize = 8;
+pub const TW_SEAT_WIND: usize = 9;
+pub const TW_ALL_CHOWS: usize = 10;
+pub const TW_MELDED_HAND: usize = 11;
+pub const TW_SINGLE_WAIT: usize = 12;
+pub const TW_LAST_TILE_DRAW: usize = 13;
+pub const TW_OUT_WITH_REPLACEMENT_TILE: usize = 14;
+pub const TW_ROBBING_THE_KONG: usize = 15;
+pub const TW_THREE_CONCEALED_PUNGS: usize = 16;
+pub const TW_ALL_PUNGS: usize = 17;
+pub const TW_HALF_FLUSH: usize = 18;
+pub const TW_LITTLE_THREE_DRAGONS: usize = 19;
+pub const TW_FOUR_CONCEALED_PUNGS: usize = 20;
+pub const TW_FIVE_CONCEALED_PUNGS: usize = 21;
+pub const TW_FULL_FLUSH: usize = 22;
+pub const TW_BIG_THREE_DRAGONS: usize = 23;
+pub const TW_LITTLE_FOUR_WINDS: usize = 24;
+pub const TW_BIG_FOUR_WINDS: usize = 25;
+pub const TW_ALL_HONORS: usize = 26;
+pub const TW_HEAVENLY_HAND: usize = 27;
+pub const TW_EARTHLY_HAND: usize = 28;
+pub const TW_DEALER: usize = 29;
+pub const TW_DEALER_STREAK: usize = 30;
+
+// 台湾麻雀 (16枚) の台 (台ID順)
+pub const TW_TAI_LIST: &[Fan] = &[
+    fan!(TW_CONCEALED_HAND, "門清", 1),
+    fan!(TW_SELF_DRAWN, "自摸", 1),
+    fan!(TW_CONCEALED_SELF_DRAWN, "門清自摸", 3),
+    fan!(TW_SEAT_FLOWER, "正花", 1),
+    fan!(TW_FLOWER_SET, "花槓", 2),
+    fan!(TW_ALL_FLOWERS, "八仙過海", 8),
+    fan!(TW_DRAGON_PUNG, "三元牌", 1),
+    fan!(TW_PREVALENT_WIND, "圈風", 1),
+    fan!(TW_SEAT_WIND, "門風", 1),
+    fan!(TW_ALL_CHOWS, "平胡", 2),
+    fan!(TW_MELDED_HAND, "全求人", 2),
+    fan!(TW_SINGLE_WAIT, "獨聽", 1),
+    fan!(TW_LAST_TILE_DRAW, "海底撈月", 1),
+    fan!(TW_OUT_WITH_REPLACEMENT_TILE, "槓上開花", 1),
+    fan!(TW_ROBBING_THE_KONG, "搶槓", 1),
+    fan!(TW_THREE_CONCEALED_PUNGS, "三暗刻", 2),
+    fan!(TW_ALL_PUNGS, "碰碰胡", 4),
+    fan!(TW_HALF_FLUSH, "混一色", 4),
+    fan!(TW_LITTLE_THREE_DRAGONS, "小三元", 4),
+    fan!(TW_FOUR_CONCEALED_PUNGS, "四暗刻", 5),
+    fan!(TW_FIVE_CONCEALED_PUNGS, "五暗刻", 8),
+    fan!(TW_FULL_FLUSH, "清一色", 8),
+    fan!(TW_BIG_THREE_DRAGONS, "大三元", 8),
+    fan!(TW_LITTLE_FOUR_WINDS, "小四喜", 8),
+    fan!(TW_BIG_FOUR_WINDS, "大四喜", 16),
+    fan!(TW_ALL_HONORS, "字一色", 16),
+    fan!(TW_HEAVENLY_HAND, "天胡", 16),
+    fan!(TW_EARTHLY_HAND, "地胡", 16),
+    // 莊家が和了または支払う場合のみ
+    fan!(TW_DEALER, "莊家", 1),
+    fan!(TW_DEALER_STREAK, "連莊", 2), // 連莊1回ごと (連N拉N)
+];
+
+// 牌姿の判定を役と共有する台 (台ID, 判定)
+const TW_PATTERNS: &[(usize, YakuFunc)] = &[
+    (TW_HALF_FLUSH, is_honitsu),
+    (TW_LITTLE_THREE_DRAGONS, is_shousangen),
+    (TW_FULL_FLUSH, is_chinitsu),
+    (TW_BIG_THREE_DRAGONS, is_daisangen),
+    (TW_LITTLE_FOUR_WINDS, is_shousuushii),
+    (TW_BIG_FOUR_WINDS, is_daisuushii),
+    (TW_ALL_HONORS, is_tsuuiisou),
+];
+
+// 和了の状況による台
+const TW_SITUATION_FANS: SituationFans = SituationFans {
+    haitei: Some(TW_LAST_TILE_DRAW),
+    houtei: None,
+    rinshan: Some(TW_OUT_WITH_REPLACEMENT_TILE),
+    chankan: Some(TW_ROBBING_THE_KONG),
+    tenhou: Some(TW_HEAVENLY_HAND),
+    tiihou: Some(TW_EARTHLY_HAND),
+    last_tile: None,
+};
+
+// 花牌の台 (花槓は正花と複合する)
+const TW_FLOWER_FANS: FlowerFans = FlowerFans {
+    seat: TW_SEAT_FLOWER,
+    set: TW_FLOWER_SET,
+    set_replaces_seat: false,
+};
+
+// 台湾麻雀のルール設定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TwRules {
+    pub base_point: Point, // 底 (0台の支払い)
+    pub tai_point: Point,  // 1台あたりの支払い
+    pub flowers: bool,     // 花牌の台を数える
+}
+
+impl Default for TwRules {
+    fn default() -> Self {
+        Self {
+            base_point: 300,
+            tai_point: 100,
+            flowers: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TwResult {
+    pub tais: Vec<(&'static Fan, usize)>, // 台と成立数 (莊家, 連莊は含まない)
+    pub tai: usize,                       // 台数 (莊家, 連莊は含まない)
+    pub dealer_tai: usize,                // 莊家と連莊の台数 (莊家が関わる支払いに加える)
+}
+
+// 16枚 (5面子1雀頭) の和了形の解釈のうち最も台数の多いものを返却
+// 連莊の回数は ctx.dealer_streak を使う
+pub fn score_tw(ctx: &HandContext, rules: &TwRules) -> Result<TwResult, MahjongError> {
+    ctx.check_mentsu(5)?;
+    ctx.check_tiles()?;
+
+    let single_wait = wait_count(ctx, |c| !five_blocks(c).is_empty()) == 1;
+    let Some(tais) = five_blocks(ctx)
+        .iter()
+        .map(|yaku_ctx| count_tais(ctx, yaku_ctx, rules, single_wait))
+        .max_by_key(|tais| fan_sum(TW_TAI_LIST, tais))
+    else {
+        return Err(MahjongError::NotWinning);
+    };
+
+    Ok(TwResult {
+        tais: merge_fans(TW_TAI_LIST, &tais),
+        tai: fan_sum(TW_TAI_LIST, &tais),
+        dealer_tai: fan_entry(TW_TAI_LIST, TW_DEALER).value
+            + ctx.dealer_streak * fan_entry(TW_TAI_LIST, TW_DEALER_STREAK).value,
+    })
+}
+
+// 各座席の点数の増減を返却 (loser が None の場合は自摸和了)
+// 支払いは 底 + 台数 * 1台, 莊家が和了または支払う場合は莊家と連莊の台を加える
+pub fn get_payments_tw(
+    result: &TwResult,
+    rules: &TwRules,
+    winner: Seat,
+    dealer: Seat,
+    loser: Option<Seat>,
+) -> Result<Vec<Point>, MahjongError> {
+    check_seats(SEAT, winner, loser)?;
+    check_seats(SEAT, dealer, None)?;
+
+    let mut deltas = vec![0; SEAT];
+    for seat in 0..SEAT {
+        if seat == winner || loser.is_some_and(|l| l != seat) {
+            continue;
+        }
+        let mut tai = result.tai;
+        if winner == dealer || seat == dealer {
+            tai += result.dealer_tai;
+        }
+        let p = rules.base_point + rules.tai_point * tai as Point;
+        deltas[seat] -= p;
+        deltas[winner] += p;
+    }
+    Ok(deltas)
+}
+
+// 5面子1雀頭の和了形の解釈
+fn five_blocks(ctx: &HandContext) -> Vec<YakuContext> {
+    detect_winning(ctx)
+        .into_iter()
+        .filter(|yaku_ctx| matches!(yaku_ctx.form(), YakuForm::FiveBlock(_)))
+        .collect()
+}
+
+// 成立する台IDを列挙する
+fn count_tais(
+    ctx: &HandContext,
+    yaku_ctx: &YakuContext,
+    rules: &TwRules,
+    single_wait: bool,
+) -> Vec<usize> {
+    let mut tais = vec![];
+    for &(id, pattern) in TW_PATTERNS {
+        if pattern(yaku_ctx) {
+            tais.push(id);
+        }
+    }
+    tais.extend(situation_fans(ctx, &TW_SITUATION_FANS));
+
+    let YakuForm::FiveBlock(fb) = yaku_ctx.form() else {
+        return tais;
+    };
+    let agari = fb.agari_block();
+    let mut chows = 0;
+    let mut concealed_pungs = 0;
+    let mut has_honor = false;
+    for (i, &Block(bt, t)) in fb.blocks().iter().enumerate() {
+        has_honor |= t.is_honor();
+        match bt {
+            BlockType::Pair => {}
+            BlockType::Shuntsu | BlockType::Chi => chows += 1,
+            _ => {
+                // ロンで完成した刻子は暗刻としない
+                if bt == BlockType::Ankan
+                    || (bt == BlockType::Koutsu && (i != agari.index || agari.closed))
+                {
+                    concealed_pungs += 1;
+                }
+                if t.is_dragon()
+                    && !tais.contains(&TW_LITTLE_THREE_DRAGONS)
+                    && !tais.contains(&TW_BIG_THREE_DRAGONS)
+                {
+                    tais.push(TW_DRAGON_PUNG);
+                }
+                if t.is_wind()
+                    && !tais.contains(&TW_LITTLE_FOUR_WINDS)
+                    && !tais.contains(&TW_BIG_FOUR_WINDS)
+                {
+                    if t.1 == ctx.bakaze {
+                        tais.push(TW_PREVALENT_WIND);
+                    }
+                    if t.1 == ctx.jikaze {
+                        tais.push(TW_SEAT_WIND);
+                    }
+                }
+            }
+        }
+    }
+    let pungs = fb.blocks().len() - 1 - chows;
+    if pungs == ctx.mentsu {
+        tais.push(TW_ALL_PUNGS);
+    }
+    match concealed_pungs {
+        3 => tais.push(TW_THREE_CONCEALED_PUNGS),
+        4 => tais.push(TW_FOUR_CONCEALED_PUNGS),
+        5 => tais.push(TW_FIVE_CONCEALED_PUNGS),
+        _ => {}
+    }
+
+    let menzen = !yaku_ctx.is_open();
+    match (menzen, ctx.tsumo) {
+        (true, true) => tais.push(TW_CONCEALED_SELF_DRAWN),
+        (true, false) => tais.push(TW_CONCEALED_HAND),
+        (false, true) => tais.push(TW_SELF_DRAWN),
+        _ => {}
+    }
+    let all_claimed = ctx.fuuro.len() == ctx.mentsu
+        && ctx
+            .fuuro
+            .iter()
+            .all(|Fuuro(ft, _)| !matches!(ft, FuuroType::Ankan));
+    if all_claimed && !ctx.tsumo {
+        tais.push(TW_MELDED_HAND);
+    }
+    let wait = yaku_ctx.wait_type();
+    if chows == ctx.mentsu
+        && !has_honor
+        && ctx.flowers.is_empty()
+        && !ctx.tsumo
+        && wait == WaitType::Ryanmen
+    {
+        tais.push(TW_ALL_CHOWS);
+    }
+    if single_wait
+        && !all_claimed
+        && matches!(
+            wait,
+            WaitType::Penchan | WaitType::Kanchan | WaitType::Tanki
+        )
+    {
+        tais.push(TW_SINGLE_WAIT);
+    }
+
+    // 八仙過海は他の花牌の台と複合しない
+    if rules.flowers {
+        if ctx.flowers.len() == 8 {
+            tais.push(TW_ALL_FLOWERS);
+        } else {
+            tais.extend(flower_fans(ctx, &TW_FLOWER_FANS));
+        }
+    }
+    tais
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tai_ids(res: &TwResult) -> Vec<(usize, usize)> {
+        res.tais.iter().map(|(t, n)| (t.id, *n)).collect()
+    }
+
+    #[test]
+    fn score_tw_works_01() {
+        // 門清, 獨聽 (16枚の5面子1雀頭)
+        let ctx = HandContext {
+            mentsu: 5,
+            ..test_ctx("123m456m789m123p456p1z+1z", false, WSO)
+        };
+        let res = score_tw(&ctx, &TwRules::default()).unwrap();
+        assert_eq!(
+            tai_ids(&res),
+            vec![(TW_CONCEALED_HAND, 1), (TW_SINGLE_WAIT, 1)]
+        );
+        assert_eq!(res.tai, 2);
+
+        // 16枚の手牌で面子数が4の場合
+        let ctx = HandContext { mentsu: 4, ..ctx };
+        assert_eq!(
+            score_tw(&ctx, &TwRules::default()).unwrap_err(),
+            MahjongError::InvalidMentsuCount(4)
+        );
+
+        // 14枚 (4面子1雀頭) の和了形は扱わない
+        let ctx = test_ctx("123m456p789s234s1z+1z", false, WSO);
+        assert_eq!(
+            score_tw(&ctx, &TwRules::default()).unwrap_err(),
+            MahjongError::InvalidMentsuCount(4)
+        );
+    }
+
+    #[test]
+    fn score_tw_works_02() {
+        // 碰碰胡, 三元牌, 門風 (南家の南), 正花 (夏)
+        let ctx = HandContext {
+            mentsu: 5,
+            ..test_ctx(
+                "333p55s77z+7z 2h (pon 111m) (pon 999m) (pon 222z)",
+                false,
+                WSO,
+            )
+        };
+        let res = score_tw(&ctx, &TwRules::default()).unwrap();
+        assert_eq!(
+            tai_ids(&res),
+            vec![
+                (TW_SEAT_FLOWER, 1),
+                (TW_DRAGON_PUNG, 1),
+                (TW_SEAT_WIND, 1),
+                (TW_ALL_PUNGS, 1)
+            ]
+        );
+        assert_eq!(res.tai, 7);
+    }
+
+    #[test]
+    fn get_payments_tw_works_01() {
+        // 連莊2回の莊家の門清自摸 (莊家と連莊で5台)
+        let ctx = HandContext {
+            mentsu: 5,
+            dealer_streak: 2,
+            ..test_ctx("123m456m789m123p456p1z+1z", true, WEA)
+        };
+        let rules = TwRules::default();
+        let res = score_tw(&ctx, &rules).unwrap();
+        assert_eq!((res.tai, res.dealer_tai), (4, 5));
+        assert_eq!(
+            get_payments_tw(&res, &rules, 0, 0, None),
+            Ok(vec![3600, -1200, -1200, -1200])
+        );
+
+        // 本場は連莊の台に影響しない
+        let ctx = HandContext { honba: 3, ..ctx };
+        assert_eq!(score_tw(&ctx, &rules).unwrap().dealer_tai, 5);
+
+        // 子の和了で莊家以外が放銃した場合は莊家の台を含まない
+        assert_eq!(
+            get_payments_tw(&res, &rules, 1, 0, Some(2)),
+            Ok(vec![0, 700, -700, 0])
+        );
+
+        // 座席番号が不正
+        assert_eq!(
+            get_payments_tw(&res, &rules, 1, 5, None),
+            Err(MahjongError::InvalidSeat(5))
+        );
+    }
+}
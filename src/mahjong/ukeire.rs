@@ -0,0 +1,104 @@
+use super::*;
+
+#[derive(Debug, Clone)]
+pub struct DiscardInfo {
+    pub discard: Tile,                 // 打牌
+    pub shanten: Shanten,              // 打牌後の向聴数
+    pub accepting: Vec<(Tile, usize)>, // (向聴数が進む牌, 残り枚数)
+    pub total: usize,                  // 受け入れ枚数の合計
+}
+
+// 13枚 (鳴きを含む) の手牌の向聴数と受け入れ牌を返却
+// visible には手牌以外で見えている牌 (鳴き, ドラ表示牌, 河など) を渡す
+pub fn calc_ukeire(
+    hand: &TileTable,
+    meld_count: usize,
+    visible: &TileTable,
+) -> (Shanten, Vec<(Tile, usize)>) {
+    let current = shanten(hand, meld_count).min;
+
+    let mut hand = *hand;
+    let mut accepting = vec![];
+    for (i, j) in tile_kinds() {
+        if hand[i][j] >= TILE {
+            continue;
+        }
+        hand[i][j] += 1;
+        if shanten(&hand, meld_count).min < current {
+            let rest = TILE.saturating_sub(hand[i][j] - 1 + visible[i][j]);
+            accepting.push((Tile(i, j), rest));
+        }
+        hand[i][j] -= 1;
+    }
+
+    (current, accepting)
+}
+
+// 14枚 (鳴きを含む) の手牌について打牌候補ごとの向聴数と受け入れを返却
+// 向聴数の小さい順, 受け入れ枚数の多い順に並べる
+pub fn analyze_discards(
+    hand: &TileTable,
+    meld_count: usize,
+    visible: &TileTable,
+) -> Vec<DiscardInfo> {
+    let mut hand = *hand;
+    let mut output = vec![];
+    for (i, j) in tile_kinds() {
+        if hand[i][j] == 0 {
+            continue;
+        }
+        hand[i][j] -= 1;
+        let (shanten, accepting) = calc_ukeire(&hand, meld_count, visible);
+        let total = accepting.iter().map(|(_, n)| n).sum();
+        output.push(DiscardInfo {
+            discard: Tile(i, j),
+            shanten,
+            accepting,
+            total,
+        });
+        hand[i][j] += 1;
+    }
+
+    output.sort_by(|a, b| {
+        a.shanten
+            .cmp(&b.shanten)
+            .then(b.total.cmp(&a.total))
+            .then(a.discard.cmp(&b.discard))
+    });
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analyze_discards_works_01() {
+        let v = analyze_discards(&to_table("123m456p789s23s11z4z"), 0, &TileTable::default());
+        assert_eq!(v[0].discard, Tile(TZ, WNO));
+        assert_eq!(v[0].shanten, 0);
+        assert_eq!(v[0].accepting, vec![(Tile(TS, 1), 4), (Tile(TS, 4), 4)]);
+        assert_eq!(v[0].total, 8);
+        assert!(v[1].shanten > 0);
+    }
+
+    #[test]
+    fn analyze_discards_works_02() {
+        // 見えている牌と手牌の分は残り枚数から引く
+        let v = analyze_discards(&to_table("123m456p789s23s11z4z"), 0, &to_table("111s"));
+        assert_eq!(v[0].accepting, vec![(Tile(TS, 1), 1), (Tile(TS, 4), 4)]);
+        assert_eq!(v[0].total, 5);
+    }
+
+    #[test]
+    fn calc_ukeire_works_01() {
+        // 1m は手牌で3枚使っている
+        let (shanten, accepting) =
+            calc_ukeire(&to_table("111m23m456p789s11z"), 0, &TileTable::default());
+        assert_eq!(shanten, 0);
+        assert_eq!(
+            accepting,
+            vec![(Tile(TM, 1), 1), (Tile(TM, 4), 4), (Tile(TZ, WEA), 2)]
+        );
+    }
+}
@@ -1,9 +1,92 @@
 use super::*;
 
+// オールマイティ (z8) は全ての牌への置き換えを試し, 和了形になるものをすべて返却
 pub fn detect_winning(ctx: &HandContext) -> Vec<YakuContext> {
+    let TileWithDora(agari_tile, agari_dora) = ctx.agari_tile;
+    let almighty_cnt = ctx
+        .hand_tiles
+        .iter()
+        .filter(|TileWithDora(t, _)| t.is_almighty())
+        .count();
+    if almighty_cnt == 0 && !agari_tile.is_almighty() {
+        return detect_winning_fixed(ctx);
+    }
+
+    let kinds: Vec<Tile> = tile_kinds().map(|(i, j)| Tile(i, j)).collect();
+    let agari_candidates = if agari_tile.is_almighty() {
+        kinds.clone()
+    } else {
+        vec![agari_tile]
+    };
+
+    let mut base_ctx = ctx.clone();
+    base_ctx
+        .hand_tiles
+        .retain(|TileWithDora(t, _)| !t.is_almighty());
+    // 置き換え前の牌の枚数 (鳴きを含む)
+    let mut fixed = TileTable::default();
+    let fuuro_tiles = ctx.fuuro.iter().flat_map(|Fuuro(_, v)| v);
+    for TileWithDora(t, _) in base_ctx.hand_tiles.iter().chain(fuuro_tiles) {
+        fixed[t.0][t.1] += 1;
+    }
+
+    let mut output = vec![];
+    let mut combinations = vec![];
+    almighty_combinations(&kinds, 0, almighty_cnt, &mut vec![], &mut combinations);
+    for combination in &combinations {
+        for &agari in &agari_candidates {
+            let mut subs_ctx = base_ctx.clone();
+            subs_ctx
+                .hand_tiles
+                .extend(combination.iter().map(|&t| TileWithDora(t, 0)));
+            subs_ctx.agari_tile = TileWithDora(agari, agari_dora);
+
+            let mut subs = combination.clone();
+            if agari_tile.is_almighty() {
+                subs.push(agari);
+            }
+            // 同じ牌が TILE 枚を超える置き換えは除く
+            let mut table = fixed;
+            for t in combination.iter().chain(std::iter::once(&agari)) {
+                table[t.0][t.1] += 1;
+            }
+            if table.iter().flatten().any(|&n| n > TILE) {
+                continue;
+            }
+            for mut yaku_ctx in detect_winning_fixed(&subs_ctx) {
+                yaku_ctx.set_almighty_substitutions(subs.clone());
+                output.push(yaku_ctx);
+            }
+        }
+    }
+
+    output
+}
+
+// 手牌のオールマイティの置き換え先の組み合わせ (重複組み合わせ) を列挙
+fn almighty_combinations(
+    kinds: &[Tile],
+    start: usize,
+    n: usize,
+    current: &mut Vec<Tile>,
+    output: &mut Vec<Vec<Tile>>,
+) {
+    if n == 0 {
+        output.push(current.clone());
+        return;
+    }
+    for k in start..kinds.len() {
+        current.push(kinds[k]);
+        almighty_combinations(kinds, k, n - 1, current, output);
+        current.pop();
+    }
+}
+
+// オールマイティを含まない手牌の和了判定
+fn detect_winning_fixed(ctx: &HandContext) -> Vec<YakuContext> {
     let fuuro_cnt = 3 * &ctx.fuuro.len();
     let hand_tiles = &ctx.hand_tiles;
-    if fuuro_cnt + hand_tiles.len() != 13 {
+    if fuuro_cnt + hand_tiles.len() != 3 * ctx.mentsu + 1 {
         return vec![];
     }
 
@@ -13,6 +96,7 @@ pub fn detect_winning(ctx: &HandContext) -> Vec<YakuContext> {
     let tsumo = ctx.tsumo;
     let TileWithDora(agari_tile, _) = ctx.agari_tile;
     let yaku_flags = ctx.yaku_flags;
+    let mentsu = ctx.mentsu;
 
     let mut output: Vec<YakuContext> = Vec::new();
 
@@ -23,13 +107,12 @@ pub fn detect_winning(ctx: &HandContext) -> Vec<YakuContext> {
     hand[agari_tile.0][agari_tile.1] += 1;
     // hand は 手牌+アガり牌 (鳴いた牌は含まない) の枚数の2次元配列
 
-    {
-        // 七対子と国士無双の判定
+    if ctx.mentsu == 4 {
+        // 七対子と国士無双の判定 (14枚の場合のみ)
         let mut pairs: Vec<Tile> = Vec::new();
         let mut kokushi_flg = true;
-        for i in 0..3 {
-            for j in 1..=9 {
-                let n = hand[i][j];
+        for (i, row) in hand.iter().enumerate().take(TZ) {
+            for (j, &n) in row.iter().enumerate().take(10).skip(1) {
                 if n == 2 {
                     pairs.push(Tile(i, j));
                 }
@@ -38,8 +121,7 @@ pub fn detect_winning(ctx: &HandContext) -> Vec<YakuContext> {
                 }
             }
         }
-        for j in 1..=7 {
-            let n = hand[TZ][j];
+        for (j, &n) in hand[TZ].iter().enumerate().take(DRE + 1).skip(1) {
             if n == 2 {
                 pairs.push(Tile(TZ, j));
             }
@@ -107,7 +189,7 @@ pub fn detect_winning(ctx: &HandContext) -> Vec<YakuContext> {
                 continue;
             }
 
-            let mut hand_tmp = hand.clone();
+            let mut hand_tmp = hand;
             hand_tmp[i][j] -= 2;
 
             // 残りの牌を面子に分けられるかどうか試す
@@ -186,16 +268,28 @@ pub fn detect_winning(ctx: &HandContext) -> Vec<YakuContext> {
                                                 }
                                             }
 
-                                            if let Some(fb) =
-                                                FiveBlock::new(blocks, tsumo, bakaze, jikaze)
-                                            {
-                                                output.push(YakuContext::new(
-                                                    hand,
-                                                    YakuForm::FiveBlock(fb),
-                                                    agari_tile,
+                                            // アガり牌の当てはめ方ごとに解釈を分ける
+                                            for agari_index in agari_block_candidates(
+                                                &blocks,
+                                                fuuro_blocks.len(),
+                                                &agari_tile,
+                                            ) {
+                                                if let Some(fb) = FiveBlock::new(
+                                                    blocks.clone(),
+                                                    agari_index,
                                                     tsumo,
-                                                    yaku_flags,
-                                                ));
+                                                    bakaze,
+                                                    jikaze,
+                                                    mentsu,
+                                                ) {
+                                                    output.push(YakuContext::new(
+                                                        hand,
+                                                        YakuForm::FiveBlock(fb),
+                                                        agari_tile,
+                                                        tsumo,
+                                                        yaku_flags,
+                                                    ));
+                                                }
                                             }
                                         }
                                     }
@@ -211,6 +305,26 @@ pub fn detect_winning(ctx: &HandContext) -> Vec<YakuContext> {
     output
 }
 
+// アガり牌が完成させたとみなせるブロックの添字 (鳴いたブロックは除く)
+// 同じ種類・同じ牌のブロックは1つにまとめる
+fn agari_block_candidates(blocks: &[Block], fuuro_cnt: usize, agari_tile: &Tile) -> Vec<usize> {
+    let mut output: Vec<usize> = vec![];
+    for i in fuuro_cnt..blocks.len() {
+        let Block(bt, t) = blocks[i];
+        if !blocks[i].contains_in_hand(agari_tile) {
+            continue;
+        }
+        if output
+            .iter()
+            .any(|&k| blocks[k].0 == bt && blocks[k].1 == t)
+        {
+            continue;
+        }
+        output.push(i);
+    }
+    output
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -241,6 +355,7 @@ mod test {
             vec![],
             vec![],
             vec![],
+            vec![],
             YakuFlags::default(),
         );
         assert_eq!(detect_winning(&ctx).len(), 1);
@@ -272,8 +387,92 @@ mod test {
             vec![],
             vec![],
             vec![],
+            vec![],
             YakuFlags::default(),
         );
         assert_eq!(detect_winning(&ctx).len(), 2);
     }
+
+    #[test]
+    fn detect_winning_works_03() {
+        // 手牌のオールマイティ
+        let ctx = test_ctx("123m456p789s23s1z8z+1z", false, WNO);
+        let v = detect_winning(&ctx);
+        let subs: Vec<&[Tile]> = v.iter().map(|y| y.almighty_substitutions()).collect();
+        assert_eq!(subs, vec![&[Tile(TS, 1)], &[Tile(TS, 4)]]);
+    }
+
+    #[test]
+    fn detect_winning_works_04() {
+        // アガり牌のオールマイティ
+        let ctx = test_ctx("123m456p789s23s11z+8z", false, WNO);
+        let v = detect_winning(&ctx);
+        let subs: Vec<&[Tile]> = v.iter().map(|y| y.almighty_substitutions()).collect();
+        assert_eq!(subs, vec![&[Tile(TS, 1)], &[Tile(TS, 4)]]);
+    }
+
+    #[test]
+    fn detect_winning_works_05() {
+        // オールマイティ2枚
+        let ctx = test_ctx("123m456p789s15z88z+1z", false, WNO);
+        let v = detect_winning(&ctx);
+        assert!(v
+            .iter()
+            .any(|y| y.almighty_substitutions() == [Tile(TZ, DWH), Tile(TZ, DWH)]));
+    }
+
+    #[test]
+    fn detect_winning_works_06() {
+        // アガり牌が順子と刻子のどちらにも含まれる場合は両方の解釈を返す
+        let ctx = test_ctx("11123m567p789s55z+1m", false, WSO);
+        let yaku_ctxs = detect_winning(&ctx);
+        let mut placements: Vec<BlockType> = yaku_ctxs
+            .iter()
+            .map(|yaku_ctx| {
+                let (Block(bt, _), agari_block) = yaku_ctx.agari_block().unwrap();
+                assert!(!agari_block.closed);
+                bt
+            })
+            .collect();
+        placements.sort_by_key(|bt| *bt as usize);
+        placements.dedup();
+        assert_eq!(placements, vec![BlockType::Shuntsu, BlockType::Koutsu]);
+    }
+
+    #[test]
+    fn detect_winning_works_07() {
+        // 16枚 (5面子1雀頭) の和了形は mentsu を5にした場合のみ判定する
+        let mut ctx = test_ctx("1122334455667m (pon 777z)+7m", true, WSO);
+        assert!(detect_winning(&ctx).is_empty());
+        ctx.mentsu = 5;
+        let yaku_ctxs = detect_winning(&ctx);
+        assert!(!yaku_ctxs.is_empty());
+        for yaku_ctx in &yaku_ctxs {
+            let YakuForm::FiveBlock(fb) = yaku_ctx.form() else {
+                panic!("unexpected form");
+            };
+            assert_eq!(fb.blocks().len(), 6);
+        }
+    }
+
+    #[test]
+    fn detect_winning_works_08() {
+        // 5枚目の牌になる置き換えは和了形としない
+        let mut ctx = test_ctx("1111m234p567s99s8z+9s", false, WSO);
+        assert!(detect_winning(&ctx).is_empty());
+
+        // 鳴きのオールマイティは不正な副露
+        assert_eq!(
+            parse_hand("123m456p789s1z+1z (pon 888z)").unwrap_err(),
+            MahjongError::InvalidMeld("pon 888z".to_string())
+        );
+        let almighty = TileWithDora(Tile(TZ, ALM), 0);
+        ctx.hand_tiles = parse_tiles("1111m234p567s9s").unwrap();
+        ctx.fuuro = vec![Fuuro(FuuroType::Pon, vec![almighty; 3])];
+        assert!(detect_winning(&ctx).is_empty());
+        assert!(matches!(
+            ctx.check_tiles(),
+            Err(MahjongError::InvalidMeld(_))
+        ));
+    }
 }
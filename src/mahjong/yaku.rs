@@ -1,9 +1,9 @@
 use super::*;
-use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct FiveBlock {
     blocks: Vec<Block>,            // すべてのブロック (アガり牌を含む)
+    agari_block: AgariBlock,       // アガり牌が完成させたブロック
     pair_tile: Tile,               // 雀頭の牌
     tsumo: bool,                   // ツモ和了
     bakaze: Tnum,                  // 場風 (東: 1, 南: 2, 西: 3, 北: 4)
@@ -15,18 +15,37 @@ pub struct FiveBlock {
 }
 
 impl FiveBlock {
-    pub fn new(blocks: Vec<Block>, tsumo: bool, bakaze: Tnum, jikaze: Tnum) -> Option<Self> {
-        if blocks.len() != 5 {
+    pub fn new(
+        blocks: Vec<Block>,
+        agari_index: usize,
+        tsumo: bool,
+        bakaze: Tnum,
+        jikaze: Tnum,
+        mentsu: usize,
+    ) -> Option<Self> {
+        // 面子の数は mentsu (通常は4, 台湾麻雀の16枚の場合は5)
+        if blocks.len() != mentsu + 1 || agari_index >= blocks.len() {
             return None;
         }
 
         if let Some(pair_tile) = blocks_to_pair_tile(&blocks) {
-            let counts = blocks_to_counts(&blocks);
+            // ロンの場合, アガり牌で完成したブロックは鳴いたものとみなす
+            let agari_block = AgariBlock {
+                index: agari_index,
+                closed: tsumo,
+            };
+            let mut counts = blocks_to_counts(&blocks);
+            if let Block(BlockType::Koutsu, _) = blocks[agari_index] {
+                if !agari_block.closed {
+                    counts.ankou_total -= 1;
+                }
+            }
             let tile_type_cnts = blocks_to_tile_type_cnts(&blocks);
             let is_open = blocks_to_is_open(&blocks);
             let iipeikou_count = blocks_to_iipeikou_count(&blocks);
             return Some(Self {
                 blocks,
+                agari_block,
                 pair_tile,
                 tsumo,
                 bakaze,
@@ -44,6 +63,75 @@ impl FiveBlock {
     pub fn is_open(&self) -> bool {
         self.is_open
     }
+
+    pub fn agari_block(&self) -> AgariBlock {
+        self.agari_block
+    }
+
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    // アガり牌 at が index のブロックを完成させた場合の待ちの形
+    fn block_wait_type(&self, index: usize, at: &Tile) -> Option<WaitType> {
+        let Block(bt, t) = &self.blocks[index];
+        if t.0 != at.0 {
+            return None;
+        }
+        match bt {
+            BlockType::Shuntsu => {
+                if t.1 + 1 == at.1 {
+                    Some(WaitType::Kanchan)
+                } else if t.1 == at.1 {
+                    Some(if t.1 == 7 {
+                        WaitType::Penchan
+                    } else {
+                        WaitType::Ryanmen
+                    })
+                } else if t.1 + 2 == at.1 {
+                    Some(if t.1 == 1 {
+                        WaitType::Penchan
+                    } else {
+                        WaitType::Ryanmen
+                    })
+                } else {
+                    None
+                }
+            }
+            BlockType::Koutsu if t == at => Some(WaitType::Shanpon),
+            BlockType::Pair if t == at => {
+                // 雀頭と隣接する順子があれば延べ単
+                let nobetan = at.is_suited()
+                    && self.blocks.iter().any(|Block(bt2, t2)| {
+                        matches!(bt2, BlockType::Shuntsu)
+                            && t2.0 == at.0
+                            && (t2.1 == at.1 + 1 || t2.1 + 3 == at.1)
+                    });
+                Some(if nobetan {
+                    WaitType::Nobetan
+                } else {
+                    WaitType::Tanki
+                })
+            }
+            _ => None,
+        }
+    }
+
+    // 暗刻か (ロンで完成した刻子は明刻)
+    fn is_ankou(&self, index: usize) -> bool {
+        match self.blocks[index].0 {
+            BlockType::Koutsu => self.agari_block.index != index || self.agari_block.closed,
+            BlockType::Ankan => true,
+            _ => false,
+        }
+    }
+}
+
+// アガり牌が完成させたブロック
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgariBlock {
+    pub index: usize, // FiveBlock のブロックの添字
+    pub closed: bool, // ツモで完成した (ロンの場合は鳴いたブロックとみなす)
 }
 
 fn blocks_to_counts(blocks: &Vec<Block>) -> Counts {
@@ -84,18 +172,15 @@ fn blocks_to_pair_tile(blocks: &Vec<Block>) -> Option<Tile> {
     let mut tile: Tile = Tile::default();
     let mut pair_cnt = 0;
     for Block(bt, t) in blocks {
-        match bt {
-            BlockType::Pair => {
-                tile = *t;
-                pair_cnt += 1;
-            }
-            _ => {}
+        if *bt == BlockType::Pair {
+            tile = *t;
+            pair_cnt += 1;
         }
     }
     if pair_cnt == 1 {
-        return Some(tile);
+        Some(tile)
     } else {
-        return None;
+        None
     }
 }
 
@@ -121,14 +206,11 @@ fn blocks_to_iipeikou_count(blocks: &Vec<Block>) -> usize {
     let mut n = 0;
     let mut shuntsu = TileTable::default();
     for Block(bt, t) in blocks {
-        match bt {
-            BlockType::Shuntsu => {
-                shuntsu[t.0][t.1] += 1;
-                if shuntsu[t.0][t.1] == 2 {
-                    n += 1;
-                }
+        if *bt == BlockType::Shuntsu {
+            shuntsu[t.0][t.1] += 1;
+            if shuntsu[t.0][t.1] == 2 {
+                n += 1;
             }
-            _ => {}
         }
     }
     n
@@ -138,10 +220,10 @@ fn check_yakuhai(blocks: &Vec<Block>) -> TileRow {
     let mut tr = TileRow::default();
     for Block(b, t) in blocks {
         match b {
-            BlockType::Koutsu | BlockType::Pon | BlockType::Minkan | BlockType::Ankan => {
-                if t.is_honor() {
-                    tr[t.1] += 1;
-                }
+            BlockType::Koutsu | BlockType::Pon | BlockType::Minkan | BlockType::Ankan
+                if t.is_honor() =>
+            {
+                tr[t.1] += 1;
             }
             _ => {}
         }
@@ -176,7 +258,7 @@ pub enum YakuForm {
     KokushiMusou,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BlockType {
     Pair,    // 雀頭
     Shuntsu, // 順子
@@ -191,6 +273,18 @@ pub enum BlockType {
 #[derive(Debug, Clone, Copy)]
 pub struct Block(pub BlockType, pub Tile);
 
+impl Block {
+    // 手牌の中のブロックで tile を含むか
+    pub fn contains_in_hand(&self, tile: &Tile) -> bool {
+        let Block(bt, t) = self;
+        match bt {
+            BlockType::Pair | BlockType::Koutsu => t == tile,
+            BlockType::Shuntsu => t.0 == tile.0 && t.1 <= tile.1 && tile.1 <= t.1 + 2,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 struct Counts {
     pair: usize,
@@ -202,7 +296,7 @@ struct Counts {
     ankan: usize,
     shuntsu_total: usize, // shuntu + chi
     koutsu_total: usize,  // koutsu + pon + minkan + ankan
-    ankou_total: usize,   // koutsu + ankan
+    ankou_total: usize,   // koutsu + ankan (ロンで完成した刻子を除く)
     kantsu_total: usize,  // minkan + ankan
 }
 
@@ -213,6 +307,7 @@ pub struct YakuContext {
     agari_tile: Tile,      // アガり牌
     tsumo: bool,           // ツモ和了
     yaku_flags: YakuFlags, // 組み合わせ以外による役 外部から設定を行う
+    almighty: Vec<Tile>,   // オールマイティの置き換え先 (手牌の順, アガり牌の場合は末尾)
 }
 
 impl YakuContext {
@@ -229,79 +324,122 @@ impl YakuContext {
             agari_tile,
             tsumo,
             yaku_flags,
+            almighty: vec![],
         }
     }
 
-    // (役一覧, 飜数, 役満倍数)を返却. 役満ではない場合,役満倍率は0, 役一覧に鳴き0飜とドラは含まない
-    pub fn calc_yaku(
-        &self,
-        yaku_enable_map: &HashMap<usize, bool>,
-    ) -> (Vec<&'static Yaku>, usize, usize) {
+    pub fn form(&self) -> &YakuForm {
+        &self.form
+    }
+
+    pub fn almighty_substitutions(&self) -> &[Tile] {
+        &self.almighty
+    }
+
+    pub(crate) fn set_almighty_substitutions(&mut self, almighty: Vec<Tile>) {
+        self.almighty = almighty;
+    }
+
+    // 役一覧, 飜数, 役満倍数を返却. 役満ではない場合,役満倍率は0, 役一覧に鳴き0飜とドラは含まない
+    pub fn calc_yaku(&self, rules: &Ruleset) -> YakuResult {
+        let is_open = match &self.form {
+            YakuForm::FiveBlock(b) => b.is_open(),
+            _ => false,
+        };
+
         let mut yaku = vec![];
+        let mut kuitan_dropped = false;
         for y in YAKU_LIST {
-            if *yaku_enable_map.get(&y.id).unwrap_or(&true) && (y.func)(self) {
-                yaku.push(y)
+            if !rules.is_yaku_enabled(y.id) || !(y.func)(self) {
+                continue;
             }
+            // 喰いタンなしの場合, 鳴いた断幺九は不成立
+            if y.id == YAKU_TANYAO && is_open && !rules.kuitan {
+                kuitan_dropped = true;
+                continue;
+            }
+            yaku.push(y)
         }
 
         let mut yakuman = vec![];
         for &y in &yaku {
-            if y.fan_close >= 13 {
+            if rules.fan_of(y).0 >= 13 {
                 yakuman.push(y);
             }
         }
 
         if !yakuman.is_empty() {
             let mut m = 0;
-            for y in &yakuman {
-                m += y.fan_close - 12;
+            for &y in &yakuman {
+                m += rules.fan_of(y).0 - 12;
+            }
+            // 役満が含まれている場合,役満以上の役のみを返却
+            YakuResult {
+                yaku: yakuman,
+                fan: 0,
+                yakuman_count: m,
+                kuitan_dropped,
             }
-            (yakuman, 0, m) // 役満が含まれている場合,役満以上の役のみを返却
         } else {
             let mut m = 0;
-            for y in &yaku {
-                m += match &self.form {
-                    YakuForm::FiveBlock(b) => {
-                        if b.is_open() {
-                            y.fan_open
-                        } else {
-                            y.fan_close
-                        }
-                    }
-                    _ => y.fan_close,
-                };
+            for &y in &yaku {
+                let (fan_close, fan_open) = rules.fan_of(y);
+                m += if is_open { fan_open } else { fan_close };
+            }
+            YakuResult {
+                yaku,
+                fan: m,
+                yakuman_count: 0,
+                kuitan_dropped,
             }
-            (yaku, m, 0) // 役満を含んでいない場合
         }
     }
 
-    pub fn calc_fu(&self) -> usize {
+    pub fn calc_fu(&self, rules: &FuRules) -> usize {
+        self.calc_fu_breakdown(rules).total
+    }
+
+    // 符の内訳 (国士無双は符を計算しないため空)
+    pub fn calc_fu_breakdown(&self, rules: &FuRules) -> FuBreakdown {
         match &self.form {
             YakuForm::FiveBlock(b) => {
+                // 副底
+                let mut items = vec![(FuItem::Futei, 20)];
+
                 if is_pinfu(self) {
-                    return if b.tsumo { 20 } else { 30 };
+                    // 平和はツモ20符, ロン30符
+                    items.push(if b.tsumo {
+                        (FuItem::PinfuTsumo, 0)
+                    } else {
+                        (FuItem::MenzenRon, 10)
+                    });
+                    return FuBreakdown::new(items);
                 }
 
-                // 副底
-                let mut fu = 20;
-
                 // 和了り方
-                fu += if b.tsumo {
-                    2 // ツモ
+                if b.tsumo {
+                    if rules.rinshan_tsumo_fu || !self.yaku_flags.rinshankaihou {
+                        items.push((FuItem::Tsumo, 2));
+                    }
                 } else if !b.is_open() {
-                    10 // 門前ロン
-                } else {
-                    0
-                };
+                    items.push((FuItem::MenzenRon, 10));
+                }
 
                 // 面子, 雀頭
-                for Block(bt, t) in &b.blocks {
-                    match bt {
+                for (i, &Block(bt, t)) in b.blocks.iter().enumerate() {
+                    // ロンで完成した刻子は明刻として扱う
+                    let bt = match bt {
+                        BlockType::Koutsu if !b.is_ankou(i) => BlockType::Pon,
+                        _ => bt,
+                    };
+                    let fu = match bt {
                         BlockType::Pair => {
-                            fu += if t.is_dragon() {
+                            if t.is_dragon() {
                                 2
                             } else if t.is_honor() {
-                                if t.1 == b.bakaze || t.1 == b.jikaze {
+                                if t.1 == b.bakaze && t.1 == b.jikaze {
+                                    rules.double_wind_pair_fu
+                                } else if t.1 == b.bakaze || t.1 == b.jikaze {
                                     2
                                 } else {
                                     0
@@ -310,52 +448,59 @@ impl YakuContext {
                                 0
                             }
                         }
-                        BlockType::Koutsu => fu += if t.is_end() { 8 } else { 4 },
-                        BlockType::Pon => fu += if t.is_end() { 4 } else { 2 },
-                        BlockType::Minkan => fu += if t.is_end() { 16 } else { 8 },
-                        BlockType::Ankan => fu += if t.is_end() { 32 } else { 16 },
-                        _ => {}
-                    }
-                }
-
-                // 待ちの形
-                let at = &self.agari_tile;
-                for Block(bt, t) in &b.blocks {
-                    if t.0 != at.0 {
-                        continue;
-                    }
-                    match bt {
-                        BlockType::Shuntsu => {
-                            // カンチャン待ち,ペンチャン7待ち,ペンチャン3待ち
-                            if t.1 + 1 == at.1
-                                || (t.1 == at.1 && at.1 == 7)
-                                || (t.1 + 2 == at.1 && at.1 == 3)
-                            {
-                                fu += 2;
-                                break;
+                        BlockType::Koutsu => {
+                            if t.is_end() {
+                                8
+                            } else {
+                                4
                             }
                         }
-                        BlockType::Koutsu => {} // シャンポン待ち
-                        BlockType::Pair => {
-                            // タンキ待ち, ノベタン待ち
-                            if t.1 == at.1 {
-                                fu += 2;
-                                break;
+                        BlockType::Pon => {
+                            if t.is_end() {
+                                4
+                            } else {
+                                2
                             }
                         }
-                        _ => {}
+                        BlockType::Minkan => {
+                            if t.is_end() {
+                                16
+                            } else {
+                                8
+                            }
+                        }
+                        BlockType::Ankan => {
+                            if t.is_end() {
+                                32
+                            } else {
+                                16
+                            }
+                        }
+                        _ => 0,
+                    };
+                    if fu > 0 {
+                        let item = match bt {
+                            BlockType::Pair => FuItem::Pair(t),
+                            _ => FuItem::Block(bt, t),
+                        };
+                        items.push((item, fu));
                     }
                 }
 
-                let fu = (fu + 9) / 10 * 10; // １の位は切り上げ
-                if fu == 20 {
-                    30 // 例外: 喰いピンフ形
-                } else {
-                    fu
+                // 待ちの形
+                let w = self.wait_type();
+                if w.fu() > 0 {
+                    items.push((FuItem::Wait(w), w.fu()));
                 }
+
+                // 例外: 喰いピンフ形は30符
+                if items.iter().map(|(_, fu)| fu).sum::<usize>() == 20 {
+                    items.push((FuItem::OpenPinfu, 10));
+                }
+                FuBreakdown::new(items)
             }
-            YakuForm::SevenPair(_) => 25,
-            _ => 0,
+            YakuForm::SevenPair(_) => FuBreakdown::new(vec![(FuItem::SevenPair, 25)]),
+            _ => FuBreakdown::default(),
         }
     }
 
@@ -365,14 +510,87 @@ impl YakuContext {
             _ => false,
         }
     }
+
+    // アガり牌が完成させたブロックとその情報 (4面子1雀頭の形のみ)
+    pub fn agari_block(&self) -> Option<(Block, AgariBlock)> {
+        match &self.form {
+            YakuForm::FiveBlock(fb) => Some((fb.blocks[fb.agari_block.index], fb.agari_block)),
+            _ => None,
+        }
+    }
+
+    // アガり牌がどのブロックを完成させたとみなせるか (手牌の中のブロックのみ)
+    pub fn wait_types(&self) -> Vec<WaitType> {
+        let mut output = vec![];
+        match &self.form {
+            YakuForm::FiveBlock(b) => {
+                for i in 0..b.blocks.len() {
+                    if let Some(w) = b.block_wait_type(i, &self.agari_tile) {
+                        output.push(w);
+                    }
+                }
+            }
+            YakuForm::SevenPair(_) | YakuForm::KokushiMusou => output.push(WaitType::Tanki),
+        }
+        output.sort();
+        output.dedup();
+        output
+    }
+
+    // この解釈でのアガり牌の待ちの形
+    pub fn wait_type(&self) -> WaitType {
+        match &self.form {
+            YakuForm::FiveBlock(b) => b
+                .block_wait_type(b.agari_block.index, &self.agari_tile)
+                .unwrap_or(WaitType::Tanki),
+            YakuForm::SevenPair(_) | YakuForm::KokushiMusou => WaitType::Tanki,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WaitType {
+    Ryanmen, // 両面
+    Kanchan, // 嵌張
+    Penchan, // 辺張
+    Shanpon, // 双碰
+    Tanki,   // 単騎
+    Nobetan, // 延べ単
 }
 
+impl fmt::Display for WaitType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            WaitType::Ryanmen => "両面",
+            WaitType::Kanchan => "嵌張",
+            WaitType::Penchan => "辺張",
+            WaitType::Shanpon => "双碰",
+            WaitType::Tanki => "単騎",
+            WaitType::Nobetan => "延べ単",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl WaitType {
+    // 待ちによる符
+    pub fn fu(&self) -> usize {
+        match self {
+            WaitType::Ryanmen | WaitType::Shanpon => 0,
+            WaitType::Kanchan | WaitType::Penchan | WaitType::Tanki | WaitType::Nobetan => 2,
+        }
+    }
+}
+
+// 役判定関数
+pub type YakuFunc = fn(&YakuContext) -> bool;
+
 pub struct Yaku {
     pub id: usize,
-    pub name: &'static str,             // 役名
-    pub func: fn(&YakuContext) -> bool, // 役判定関数
-    pub fan_close: usize,               // 鳴きなしの飜
-    pub fan_open: usize,                // 鳴きありの飜(食い下がり)
+    pub name: &'static str, // 役名
+    pub func: YakuFunc,     // 役判定関数
+    pub fan_close: usize,   // 鳴きなしの飜
+    pub fan_open: usize,    // 鳴きありの飜(食い下がり)
 }
 
 impl fmt::Debug for Yaku {
@@ -381,6 +599,25 @@ impl fmt::Debug for Yaku {
     }
 }
 
+// 個別に参照する役ID
+pub const YAKU_TANYAO: usize = 12;
+pub const YAKU_RENHOU: usize = 59;
+pub const YAKU_NAGASHI_MANGAN: usize = 60;
+
+// 和了形の解釈ごとの役の判定結果
+#[derive(Debug)]
+pub struct YakuResult {
+    pub yaku: Vec<&'static Yaku>, // 役一覧 (鳴き0飜とドラは含まない)
+    pub fan: usize,               // 飜数 (役満の場合は0)
+    pub yakuman_count: usize,     // 役満倍数 (役満ではない場合は0)
+    pub kuitan_dropped: bool,     // 喰いタンなしにより鳴いた断幺九を除いた
+}
+
+// 役IDから役を返却
+pub fn yaku_by_id(id: usize) -> Option<&'static Yaku> {
+    YAKU_LIST.iter().find(|y| y.id == id)
+}
+
 macro_rules! yaku {
     ($id: expr, $n: expr, $f: expr, $c: expr, $o: expr) => {
         Yaku {
@@ -405,7 +642,7 @@ const YAKU_LIST: &[Yaku] = &[
     yaku!(11, "場風 南", is_bakaze_s, 1, 1),
     yaku!(11, "場風 西", is_bakaze_w, 1, 1),
     yaku!(11, "場風 北", is_bakaze_n, 1, 1),
-    yaku!(12, "断幺九", is_tanyaochuu, 1, 1),
+    yaku!(YAKU_TANYAO, "断幺九", is_tanyaochuu, 1, 1),
     yaku!(13, "一盃口", is_iipeikou, 1, 0),
     yaku!(14, "平和", is_pinfu, 1, 0),
     yaku!(15, "混全帯幺九", is_chanta, 2, 1),
@@ -447,6 +684,10 @@ const YAKU_LIST: &[Yaku] = &[
     yaku!(30, "一発", is_ippatsu, 1, 0),
     yaku!(35, "天和", is_tenhou, 13, 13),
     yaku!(36, "地和", is_tiihou, 13, 13),
+    yaku!(YAKU_RENHOU, "人和", is_renhou, 5, 5),
+    yaku!(YAKU_NAGASHI_MANGAN, "流し満貫", is_nagashimangan, 5, 5),
+    yaku!(61, "オープン立直", is_openriichi, 1, 0),
+    yaku!(62, "オープン立直放銃", is_openriichihoujuu, 13, 0),
     // ローカル役
     yaku!(51, "十二落抬", is_shiiaruraotai, 1, 1),
     yaku!(52, "五門斉", is_uumenchii, 2, 2),
@@ -456,6 +697,8 @@ const YAKU_LIST: &[Yaku] = &[
     yaku!(56, "大竹林", is_daichikurin, 13, 0),
     yaku!(57, "大数隣", is_daisuurin, 13, 0),
     yaku!(58, "大七星", is_daichiishin, 14, 0),
+    yaku!(63, "燕返し", is_tsubamegaeshi, 1, 1),
+    yaku!(64, "槓振り", is_kanburi, 1, 1),
 ];
 
 // 場風
@@ -582,27 +825,7 @@ fn is_pinfu(ctx: &YakuContext) -> bool {
             }
 
             // 上がり牌の両面待ち判定
-            let at = &ctx.agari_tile;
-            if at.is_honor() {
-                return false;
-            }
-            for Block(bt, t) in &b.blocks {
-                match bt {
-                    BlockType::Shuntsu => {
-                        if t.0 == at.0 {
-                            if t.1 == at.1 && at.1 < 7 {
-                                return true;
-                            }
-                            if t.1 + 2 == at.1 && at.1 > 3 {
-                                return true;
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-
-            false
+            ctx.wait_type() == WaitType::Ryanmen
         }
         _ => false,
     }
@@ -663,21 +886,14 @@ fn is_sanshokudoujun(ctx: &YakuContext) -> bool {
             let mut shuntsu_cnt = [[false; 7]; 3];
             for Block(b, t) in &b.blocks {
                 match b {
-                    BlockType::Shuntsu | BlockType::Chi => {
-                        if t.is_suited() {
-                            shuntsu_cnt[t.0][t.1 - 1] = true;
-                        }
+                    BlockType::Shuntsu | BlockType::Chi if t.is_suited() => {
+                        shuntsu_cnt[t.0][t.1 - 1] = true;
                     }
                     _ => {}
                 }
             }
 
-            for i in 0..7 {
-                if shuntsu_cnt[0][i] && shuntsu_cnt[1][i] && shuntsu_cnt[2][i] {
-                    return true;
-                }
-            }
-            false
+            (0..7).any(|i| shuntsu_cnt[0][i] && shuntsu_cnt[1][i] && shuntsu_cnt[2][i])
         }
         _ => false,
     }
@@ -694,21 +910,16 @@ fn is_sanshokudoukou(ctx: &YakuContext) -> bool {
             let mut koutsu_cnt = [[false; 9]; 3];
             for Block(b, t) in &b.blocks {
                 match b {
-                    BlockType::Koutsu | BlockType::Pon | BlockType::Minkan | BlockType::Ankan => {
-                        if t.is_suited() {
-                            koutsu_cnt[t.0][t.1 - 1] = true;
-                        }
+                    BlockType::Koutsu | BlockType::Pon | BlockType::Minkan | BlockType::Ankan
+                        if t.is_suited() =>
+                    {
+                        koutsu_cnt[t.0][t.1 - 1] = true;
                     }
                     _ => {}
                 }
             }
 
-            for i in 0..9 {
-                if koutsu_cnt[0][i] && koutsu_cnt[1][i] && koutsu_cnt[2][i] {
-                    return true;
-                }
-            }
-            false
+            (0..9).any(|i| koutsu_cnt[0][i] && koutsu_cnt[1][i] && koutsu_cnt[2][i])
         }
         _ => false,
     }
@@ -780,7 +991,7 @@ fn is_junchan(ctx: &YakuContext) -> bool {
 }
 
 // 混老頭
-fn is_honroutou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_honroutou(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => {
             let mut has_honor = false;
@@ -824,7 +1035,7 @@ fn is_honroutou(ctx: &YakuContext) -> bool {
 }
 
 // 清老頭
-fn is_chinroutou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_chinroutou(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => {
             for Block(b, t) in &b.blocks {
@@ -854,7 +1065,7 @@ fn is_chinroutou(ctx: &YakuContext) -> bool {
 }
 
 // 対々和
-fn is_toitoihou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_toitoihou(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => b.counts.koutsu_total == 4,
         _ => false,
@@ -864,41 +1075,27 @@ fn is_toitoihou(ctx: &YakuContext) -> bool {
 // 三暗刻
 fn is_sanankou(ctx: &YakuContext) -> bool {
     match &ctx.form {
-        YakuForm::FiveBlock(b) => {
-            if b.counts.ankou_total < 3 {
-                return false;
-            }
-
-            let mut cnt = 0;
-            for Block(bt, t) in &b.blocks {
-                if let BlockType::Koutsu = bt {
-                    if !ctx.tsumo && ctx.agari_tile == *t {
-                        continue;
-                    }
-                    cnt += 1;
-                }
-            }
-
-            cnt == 3
-        }
+        YakuForm::FiveBlock(b) => b.counts.ankou_total == 3,
         _ => false,
     }
 }
 
 // 四暗刻
-fn is_suuankou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_suuankou(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => {
-            b.counts.ankou_total == 4 && ctx.agari_tile != b.pair_tile && ctx.tsumo
+            b.counts.ankou_total == 4 && b.blocks[b.agari_block.index].0 != BlockType::Pair
         }
         _ => false,
     }
 }
 
 // 四暗刻単騎
-fn is_suuankoutanki(ctx: &YakuContext) -> bool {
+pub(crate) fn is_suuankoutanki(ctx: &YakuContext) -> bool {
     match &ctx.form {
-        YakuForm::FiveBlock(b) => b.counts.ankou_total == 4 && ctx.agari_tile == b.pair_tile,
+        YakuForm::FiveBlock(b) => {
+            b.counts.ankou_total == 4 && b.blocks[b.agari_block.index].0 == BlockType::Pair
+        }
         _ => false,
     }
 }
@@ -912,7 +1109,7 @@ fn is_sankantsu(ctx: &YakuContext) -> bool {
 }
 
 // 四槓子
-fn is_suukantsu(ctx: &YakuContext) -> bool {
+pub(crate) fn is_suukantsu(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => b.counts.kantsu_total == 4,
         _ => false,
@@ -920,7 +1117,7 @@ fn is_suukantsu(ctx: &YakuContext) -> bool {
 }
 
 // 混一色
-fn is_honitsu(ctx: &YakuContext) -> bool {
+pub(crate) fn is_honitsu(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => {
             use std::cmp::min;
@@ -943,7 +1140,7 @@ fn is_honitsu(ctx: &YakuContext) -> bool {
 }
 
 // 清一色
-fn is_chinitsu(ctx: &YakuContext) -> bool {
+pub(crate) fn is_chinitsu(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => {
             use std::cmp::min;
@@ -966,17 +1163,17 @@ fn is_chinitsu(ctx: &YakuContext) -> bool {
 }
 
 // 小三元
-fn is_shousangen(ctx: &YakuContext) -> bool {
+pub(crate) fn is_shousangen(ctx: &YakuContext) -> bool {
     match &ctx.form {
-        YakuForm::FiveBlock(b) => has_all_dragons(&b) && b.pair_tile.is_dragon(),
+        YakuForm::FiveBlock(b) => has_all_dragons(b) && b.pair_tile.is_dragon(),
         _ => false,
     }
 }
 
 // 大三元
-fn is_daisangen(ctx: &YakuContext) -> bool {
+pub(crate) fn is_daisangen(ctx: &YakuContext) -> bool {
     match &ctx.form {
-        YakuForm::FiveBlock(b) => has_all_dragons(&b) && !b.pair_tile.is_dragon(),
+        YakuForm::FiveBlock(b) => has_all_dragons(b) && !b.pair_tile.is_dragon(),
         _ => false,
     }
 }
@@ -1004,17 +1201,17 @@ fn has_all_dragons(b: &FiveBlock) -> bool {
 }
 
 // 小四喜
-fn is_shousuushii(ctx: &YakuContext) -> bool {
+pub(crate) fn is_shousuushii(ctx: &YakuContext) -> bool {
     match &ctx.form {
-        YakuForm::FiveBlock(b) => has_all_winds(&b) && b.pair_tile.is_wind(),
+        YakuForm::FiveBlock(b) => has_all_winds(b) && b.pair_tile.is_wind(),
         _ => false,
     }
 }
 
 // 大四喜
-fn is_daisuushii(ctx: &YakuContext) -> bool {
+pub(crate) fn is_daisuushii(ctx: &YakuContext) -> bool {
     match &ctx.form {
-        YakuForm::FiveBlock(b) => has_all_winds(&b) && !b.pair_tile.is_wind(),
+        YakuForm::FiveBlock(b) => has_all_winds(b) && !b.pair_tile.is_wind(),
         _ => false,
     }
 }
@@ -1077,7 +1274,7 @@ fn is_ryuuiisou(ctx: &YakuContext) -> bool {
 }
 
 // 字一色
-fn is_tsuuiisou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_tsuuiisou(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::FiveBlock(b) => {
             use std::cmp::min;
@@ -1100,14 +1297,14 @@ fn is_tsuuiisou(ctx: &YakuContext) -> bool {
 }
 
 // 九蓮宝燈
-fn is_chuurenpoutou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_chuurenpoutou(ctx: &YakuContext) -> bool {
     let at = &ctx.agari_tile;
     let cnt = ctx.hand[at.0][at.1];
     is_chuurenpoutou_cmn(ctx) && (cnt == 1 || cnt == 3)
 }
 
 // 純正九蓮宝燈
-fn is_junseichuurenpoutou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_junseichuurenpoutou(ctx: &YakuContext) -> bool {
     let at = &ctx.agari_tile;
     let cnt = ctx.hand[at.0][at.1];
     is_chuurenpoutou_cmn(ctx) && (cnt == 2 || cnt == 4)
@@ -1136,19 +1333,14 @@ fn is_chuurenpoutou_cmn(ctx: &YakuContext) -> bool {
             if h[tile_type][1] < 3 || h[tile_type][9] < 3 {
                 return false;
             }
-            for n in 2..=8 {
-                if h[tile_type][n] == 0 {
-                    return false;
-                }
-            }
-            true
+            h[tile_type][2..=8].iter().all(|&n| n > 0)
         }
         _ => false,
     }
 }
 
 // 国士無双
-fn is_kokushimusou(ctx: &YakuContext) -> bool {
+pub(crate) fn is_kokushimusou(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::KokushiMusou => {
             let at = &ctx.agari_tile;
@@ -1160,7 +1352,7 @@ fn is_kokushimusou(ctx: &YakuContext) -> bool {
 }
 
 // 国士無双十三面待ち
-fn is_kokushimusoujuusanmenmachi(ctx: &YakuContext) -> bool {
+pub(crate) fn is_kokushimusoujuusanmenmachi(ctx: &YakuContext) -> bool {
     match &ctx.form {
         YakuForm::KokushiMusou => {
             let at = &ctx.agari_tile;
@@ -1173,10 +1365,7 @@ fn is_kokushimusoujuusanmenmachi(ctx: &YakuContext) -> bool {
 
 // 七対子
 fn is_sevenpair(ctx: &YakuContext) -> bool {
-    match &ctx.form {
-        YakuForm::SevenPair(_) => true,
-        _ => false,
-    }
+    matches!(&ctx.form, YakuForm::SevenPair(_))
 }
 
 // 門前自摸
@@ -1229,6 +1418,36 @@ fn is_tiihou(ctx: &YakuContext) -> bool {
     ctx.yaku_flags.tiihou
 }
 
+// 人和 (既定は満貫で他の役と複合しない, 役満とする場合は飜の変更で13を設定する)
+fn is_renhou(ctx: &YakuContext) -> bool {
+    ctx.yaku_flags.renhou
+}
+
+// 流し満貫
+fn is_nagashimangan(ctx: &YakuContext) -> bool {
+    ctx.yaku_flags.nagashi_mangan
+}
+
+// オープン立直 (立直に1飜を追加)
+fn is_openriichi(ctx: &YakuContext) -> bool {
+    ctx.yaku_flags.open_riichi && !is_openriichihoujuu(ctx)
+}
+
+// オープン立直にリーチしていない他家が放銃した場合は役満
+fn is_openriichihoujuu(ctx: &YakuContext) -> bool {
+    ctx.yaku_flags.open_riichi && ctx.yaku_flags.open_riichi_non_riichi_loser && !ctx.tsumo
+}
+
+// 燕返し
+fn is_tsubamegaeshi(ctx: &YakuContext) -> bool {
+    ctx.yaku_flags.tsubamegaeshi
+}
+
+// 槓振り
+fn is_kanburi(ctx: &YakuContext) -> bool {
+    ctx.yaku_flags.kanburi
+}
+
 // 十二落抬
 fn is_shiiaruraotai(ctx: &YakuContext) -> bool {
     match &ctx.form {
@@ -1241,7 +1460,7 @@ fn is_shiiaruraotai(ctx: &YakuContext) -> bool {
                     _ => {}
                 }
             }
-            return true;
+            true
         }
         _ => false,
     }
@@ -1303,25 +1522,17 @@ fn is_sanrenkou(ctx: &YakuContext) -> bool {
             let mut koutsu_table: [[usize; 10]; 3] = [[0; 10]; 3];
             for Block(bt, t) in &b.blocks {
                 match bt {
-                    BlockType::Koutsu | BlockType::Pon | BlockType::Ankan | BlockType::Minkan => {
-                        if t.0 < TZ {
-                            koutsu_table[t.0][t.1] += 1;
-                        }
-                    }
-                    _ => {}
-                }
-            }
-            for i in 0..3 {
-                for j in 1..=7 {
-                    if koutsu_table[i][j] >= 1
-                        && koutsu_table[i][j + 1] >= 1
-                        && koutsu_table[i][j + 2] >= 1
+                    BlockType::Koutsu | BlockType::Pon | BlockType::Ankan | BlockType::Minkan
+                        if t.0 < TZ =>
                     {
-                        return true;
+                        koutsu_table[t.0][t.1] += 1;
                     }
+                    _ => {}
                 }
             }
-            false
+            koutsu_table
+                .iter()
+                .any(|row| row[1..].windows(3).any(|w| w.iter().all(|&n| n >= 1)))
         }
         _ => false,
     }
@@ -1334,22 +1545,13 @@ fn is_isshokusanjun(ctx: &YakuContext) -> bool {
             let mut shuntsu_table: [[usize; 10]; 3] = [[0; 10]; 3];
             for Block(bt, t) in &b.blocks {
                 match bt {
-                    BlockType::Shuntsu | BlockType::Chi => {
-                        if t.0 < TZ {
-                            shuntsu_table[t.0][t.1] += 1;
-                        }
+                    BlockType::Shuntsu | BlockType::Chi if t.0 < TZ => {
+                        shuntsu_table[t.0][t.1] += 1;
                     }
                     _ => {}
                 }
             }
-            for i in 0..3 {
-                for j in 1..=7 {
-                    if shuntsu_table[i][j] >= 3 {
-                        return true;
-                    }
-                }
-            }
-            false
+            shuntsu_table.iter().flatten().any(|&n| n >= 3)
         }
         _ => false,
     }